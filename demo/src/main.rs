@@ -1,4 +1,4 @@
-use tokenizer::split_tokens;
+use tokenizer::{split_tokens, TokenDump};
 
 pub fn main() {
     [
@@ -20,12 +20,13 @@ sus";"#,
         "let _ := 5;",
         "34_2 432.2_34 234.count_ones() 3424.",
         "240",
+        "let a := 1;\r\nlet b := \"x\\\r\ny\";\rlet c := a + b;\n",
     ]
     .into_iter()
     .for_each(|string| {
-        println!(
-            "{string:?}: {:?}",
-            split_tokens(string).collect::<Result<Vec<_>, _>>()
-        )
+        let (tokens, errors) = split_tokens(string).tokens_and_errors();
+        print!("{string:?}:\n{}", TokenDump::new(&tokens));
+        errors.iter().for_each(|error| println!("error: {error}"));
+        println!();
     });
 }
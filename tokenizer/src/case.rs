@@ -0,0 +1,27 @@
+/// How identifiers starting with a letter from a script without case, such as `日本` or `عدد`, are
+/// classified.
+///
+/// Cased identifiers are proper if they start with an uppercase or titlecase letter, so `Foo` and
+/// `ǅemal` are proper and `foo` is improper. Titlecase letters also count as caps in improper
+/// identifiers. Letters without case are never caps, so they can appear anywhere in either kind.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum UncasedIdents {
+    /// Lex them as [`TokenType::Ident`](crate::TokenType::Ident). A proper identifier can still be
+    /// written by starting it with an uppercase letter, as in `T型`.
+    #[default]
+    Improper,
+    /// Lex them as [`TokenType::ProperIdent`](crate::TokenType::ProperIdent). An improper
+    /// identifier can still be written by starting it with `_` or a lowercase letter.
+    Proper,
+}
+
+/// Whether `c` is an uppercase or titlecase letter.
+pub(crate) fn is_caps(c: char) -> bool {
+    c.is_uppercase() || (!c.is_lowercase() && !c.to_lowercase().eq([c]))
+}
+
+/// Whether `c` has no case at all, like the letters of most scripts other than Latin, Greek,
+/// Cyrillic and Armenian.
+pub(crate) fn is_uncased(c: char) -> bool {
+    !c.is_uppercase() && !c.is_lowercase() && c.to_lowercase().eq([c]) && c.to_uppercase().eq([c])
+}
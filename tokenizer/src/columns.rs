@@ -0,0 +1,111 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+
+use crate::{ByteSpan, LineColumn, Span};
+
+/// What a column counts. The lexer counts [`ColumnUnit::Scalars`]; use a [`LineIndex`] to get
+/// positions in any other unit.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ColumnUnit {
+    Bytes,
+    /// Unicode scalar values, i.e. `char`s
+    Scalars,
+    /// UTF-16 code units, as used by the Language Server Protocol
+    Utf16,
+    /// Extended grapheme clusters, roughly what a user sees as one character
+    Graphemes,
+    /// Terminal columns, with wide chars taking two and tabs advancing to the next multiple of
+    /// `tab_stop`
+    DisplayWidth {
+        tab_stop: usize,
+    },
+}
+
+impl ColumnUnit {
+    /// Returns the column reached after `line_prefix`, which must start at the beginning of a line.
+    #[must_use]
+    pub fn measure(self, line_prefix: &str) -> usize {
+        match self {
+            Self::Bytes => line_prefix.len(),
+            Self::Scalars => line_prefix.chars().count(),
+            Self::Utf16 => line_prefix.chars().map(char::len_utf16).sum(),
+            Self::Graphemes => line_prefix.graphemes(true).count(),
+            Self::DisplayWidth { tab_stop } => {
+                let tab_stop = tab_stop.max(1);
+                line_prefix.chars().fold(0, |column, c| match c {
+                    '\t' => (column / tab_stop + 1) * tab_stop,
+                    c => column + c.width().unwrap_or(0),
+                })
+            }
+        }
+    }
+}
+
+/// The line starts of a source, for converting byte offsets to [`LineColumn`]s in any
+/// [`ColumnUnit`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    #[must_use]
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            line_starts: std::iter::once(0)
+                .chain(
+                    source
+                        .match_indices(['\n', '\r'])
+                        .filter(|&(i, c)| c == "\n" || !source[i..].starts_with("\r\n"))
+                        .map(|(i, _)| i + 1),
+                )
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    /// Returns the position of byte `offset`, or `None` if it is out of bounds or not on a char
+    /// boundary.
+    #[must_use]
+    pub fn line_column(&self, offset: usize, unit: ColumnUnit) -> Option<LineColumn> {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        self.source
+            .get(self.line_starts[line]..offset)
+            .map(|prefix| LineColumn::new(line, unit.measure(prefix)))
+    }
+
+    /// Returns the span covering `bytes`, with columns counted in `unit`.
+    #[must_use]
+    pub fn span(&self, bytes: ByteSpan, unit: ColumnUnit) -> Option<Span> {
+        Some(Span::new(
+            self.line_column(bytes.start, unit)?,
+            self.line_column(bytes.end, unit)?,
+            bytes,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_starts_on_mixed_line_endings() {
+        let index = LineIndex::new("a\r\nb\rc\nd\r\n");
+        assert_eq!(index.line_starts, [0, 3, 5, 7, 10]);
+        assert_eq!(
+            index.line_column(4, ColumnUnit::Scalars),
+            Some(LineColumn::new(1, 1))
+        );
+        assert_eq!(
+            index.line_column(8, ColumnUnit::Scalars),
+            Some(LineColumn::new(3, 1))
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::UncasedIdents;
+
+/// Options for [`SplitTokens::with_config`](crate::SplitTokens::with_config).
+///
+/// The default matches [`split_tokens`](crate::split_tokens).
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct LexerConfig {
+    pub(crate) unicode_aliases: bool,
+    pub(crate) case_rules: bool,
+    pub(crate) uncased_idents: UncasedIdents,
+    pub(crate) comments: bool,
+    pub(crate) trivia: bool,
+}
+
+impl LexerConfig {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            unicode_aliases: true,
+            case_rules: true,
+            uncased_idents: UncasedIdents::Improper,
+            comments: true,
+            trivia: false,
+        }
+    }
+
+    /// Whether `≔ ∧ ∨ ¬ 不 ≥ ≤` lex as the puncts they stand for. When disabled, they lex like any
+    /// other char, so `不` is part of an identifier and the rest are invalid.
+    #[must_use]
+    pub fn unicode_aliases(mut self, enabled: bool) -> Self {
+        self.unicode_aliases = enabled;
+        self
+    }
+
+    /// Whether caps in identifiers and underscores in proper identifiers are errors.
+    #[must_use]
+    pub fn case_rules(mut self, enabled: bool) -> Self {
+        self.case_rules = enabled;
+        self
+    }
+
+    /// How identifiers starting with a letter without case are classified.
+    #[must_use]
+    pub fn uncased_idents(mut self, policy: UncasedIdents) -> Self {
+        self.uncased_idents = policy;
+        self
+    }
+
+    /// Whether comments are yielded as tokens.
+    #[must_use]
+    pub fn comments(mut self, enabled: bool) -> Self {
+        self.comments = enabled;
+        self
+    }
+
+    /// Whether whitespace, newlines and the text skipped after errors are yielded as
+    /// [`TokenType::Trivia`](crate::TokenType::Trivia) tokens.
+    #[must_use]
+    pub fn trivia(mut self, enabled: bool) -> Self {
+        self.trivia = enabled;
+        self
+    }
+
+    /// See [`LexerConfig::unicode_aliases`].
+    #[must_use]
+    pub fn is_unicode_aliases(&self) -> bool {
+        self.unicode_aliases
+    }
+
+    /// See [`LexerConfig::case_rules`].
+    #[must_use]
+    pub fn is_case_rules(&self) -> bool {
+        self.case_rules
+    }
+
+    /// See [`LexerConfig::uncased_idents`].
+    #[must_use]
+    pub fn uncased_idents_policy(&self) -> UncasedIdents {
+        self.uncased_idents
+    }
+
+    /// See [`LexerConfig::comments`].
+    #[must_use]
+    pub fn is_comments(&self) -> bool {
+        self.comments
+    }
+
+    /// See [`LexerConfig::trivia`].
+    #[must_use]
+    pub fn is_trivia(&self) -> bool {
+        self.trivia
+    }
+}
+
+impl Default for LexerConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,261 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::iter::FusedIterator;
+
+use crate::{ByteSpan, Keyword, ParseTokenError, Punct, Span, SplitTokens, Token, TokenType};
+
+/// A cursor over the tokens of a [`SplitTokens`] with unbounded lookahead and backtracking, for
+/// hand-written parsers.
+///
+/// Lexing errors are skipped over and collected in [`TokenCursor::errors`]. Every token read is
+/// kept, so rewinding never lexes anything twice.
+#[derive(Clone, Debug)]
+pub struct TokenCursor<'a> {
+    tokens: SplitTokens<'a>,
+    buffer: Vec<Token<'a>>,
+    position: usize,
+    errors: Vec<ParseTokenError<'a>>,
+}
+
+/// A position in a [`TokenCursor`] to [`rewind`](TokenCursor::rewind) to.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Checkpoint(usize);
+
+impl<'a> TokenCursor<'a> {
+    #[must_use]
+    pub fn new(tokens: SplitTokens<'a>) -> Self {
+        Self {
+            tokens,
+            buffer: Vec::new(),
+            position: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    /// The lexing errors met so far.
+    #[must_use]
+    pub fn errors(&self) -> &[ParseTokenError<'a>] {
+        &self.errors
+    }
+
+    /// Returns the next token without consuming it.
+    pub fn peek(&mut self) -> Option<Token<'a>> {
+        self.peek_nth(0)
+    }
+
+    /// Returns the token `k` places after the next one without consuming anything, so
+    /// `peek_nth(0)` is the next token.
+    pub fn peek_nth(&mut self, k: usize) -> Option<Token<'a>> {
+        let index = self.position + k;
+        while self.buffer.len() <= index {
+            match self.tokens.next()? {
+                Ok(token) => self.buffer.push(token),
+                Err(error) => self.errors.push(error),
+            }
+        }
+        Some(self.buffer[index])
+    }
+
+    #[must_use]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.position)
+    }
+
+    /// Moves back to `checkpoint`, so the tokens read since are read again.
+    pub fn rewind(&mut self, checkpoint: Checkpoint) {
+        self.position = checkpoint.0;
+    }
+
+    /// Consumes the next token if it has type `token_type`. [`TokenType::Keyword`] also matches
+    /// the same keyword lexed as a [`TokenType::ContextualKeyword`], and [`TokenType::Ident`]
+    /// matches any contextual keyword, as they can be used as identifiers.
+    pub fn eat(&mut self, token_type: TokenType) -> Option<Token<'a>> {
+        let token = self.peek()?;
+        is_match(token.token_type, token_type).then(|| {
+            self.position += 1;
+            token
+        })
+    }
+
+    /// Consumes the next token if it has type `token_type`, as with [`TokenCursor::eat`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the span of the token found instead, or the end of the input.
+    pub fn expect(&mut self, token_type: TokenType) -> Result<Token<'a>, ExpectTokenError<'a>> {
+        match self.peek() {
+            Some(token) if is_match(token.token_type, token_type) => {
+                self.position += 1;
+                Ok(token)
+            }
+            Some(token) => Err(ExpectTokenError::Unexpected(token_type, token)),
+            None => Err(ExpectTokenError::UnexpectedEnd(token_type, self.end_span())),
+        }
+    }
+
+    /// Consumes the next token if it is `punct`.
+    ///
+    /// # Errors
+    ///
+    /// See [`TokenCursor::expect`].
+    pub fn expect_punct(&mut self, punct: Punct) -> Result<Token<'a>, ExpectTokenError<'a>> {
+        self.expect(TokenType::Punct(punct))
+    }
+
+    /// Consumes the next token if it is `keyword`, contextual or not.
+    ///
+    /// # Errors
+    ///
+    /// See [`TokenCursor::expect`].
+    pub fn expect_keyword(&mut self, keyword: Keyword) -> Result<Token<'a>, ExpectTokenError<'a>> {
+        self.expect(TokenType::Keyword(keyword))
+    }
+
+    /// The empty span at the end of the input.
+    fn end_span(&self) -> Span {
+        let (line_column, offset) = (self.tokens.line_column(), self.tokens.offset());
+        Span::new(line_column, line_column, ByteSpan::new(offset, offset))
+    }
+}
+
+fn is_match(found: TokenType, expected: TokenType) -> bool {
+    match (found, expected) {
+        (TokenType::ContextualKeyword(found), TokenType::Keyword(expected)) => found == expected,
+        (found, TokenType::Ident) => found.is_ident(),
+        _ => found == expected,
+    }
+}
+
+impl<'a> From<SplitTokens<'a>> for TokenCursor<'a> {
+    fn from(tokens: SplitTokens<'a>) -> Self {
+        Self::new(tokens)
+    }
+}
+
+impl<'a> Iterator for TokenCursor<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.peek()?;
+        self.position += 1;
+        Some(token)
+    }
+}
+
+impl FusedIterator for TokenCursor<'_> {}
+
+/// The error returned by [`TokenCursor::expect`] and its variants, holding the expected token type.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ExpectTokenError<'a> {
+    Unexpected(TokenType, Token<'a>),
+    UnexpectedEnd(TokenType, Span),
+}
+
+impl ExpectTokenError<'_> {
+    /// The span of the unexpected token, or the empty span at the end of the input.
+    #[must_use]
+    pub fn span(&self) -> Span {
+        match self {
+            Self::Unexpected(_, token) => token.span,
+            Self::UnexpectedEnd(_, span) => *span,
+        }
+    }
+}
+
+impl Display for ExpectTokenError<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.span().start)?;
+        match self {
+            Self::Unexpected(expected, token) => write!(
+                f,
+                "Expected {expected}, found {} {:?}",
+                token.token_type, token.lexeme
+            ),
+            Self::UnexpectedEnd(expected, _) => {
+                write!(f, "Expected {expected}, found end of input")
+            }
+        }
+    }
+}
+
+impl Error for ExpectTokenError<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{split_tokens, LineColumn};
+
+    fn cursor(input: &str) -> TokenCursor<'_> {
+        TokenCursor::new(split_tokens(input))
+    }
+
+    #[test]
+    fn peek_nth_looks_ahead_without_consuming() {
+        let mut cursor = cursor("a $ b c");
+        assert_eq!(cursor.peek_nth(2).map(|token| token.lexeme), Some("c"));
+        assert_eq!(cursor.peek_nth(3), None);
+        assert_eq!(cursor.errors().len(), 1);
+        assert_eq!(cursor.peek().map(|token| token.lexeme), Some("a"));
+        assert_eq!(
+            cursor.map(|token| token.lexeme).collect::<Vec<_>>(),
+            ["a", "b", "c"]
+        );
+    }
+
+    #[test]
+    fn rewind_reads_tokens_again() {
+        let mut cursor = cursor("a b c");
+        cursor.next();
+        let checkpoint = cursor.checkpoint();
+        assert_eq!(cursor.nth(1).map(|token| token.lexeme), Some("c"));
+        assert_eq!(cursor.next(), None);
+        cursor.rewind(checkpoint);
+        assert_eq!(cursor.next().map(|token| token.lexeme), Some("b"));
+        assert!(cursor.checkpoint() > checkpoint);
+    }
+
+    #[test]
+    fn contextual_keywords_are_idents_and_keywords() {
+        let mut cursor = cursor("x.type where");
+        assert!(cursor.expect(TokenType::Ident).is_ok());
+        assert!(cursor.expect_punct(Punct::Dot).is_ok());
+        assert_eq!(
+            cursor.expect(TokenType::Ident).map(|token| token.lexeme),
+            Ok("type")
+        );
+        assert_eq!(
+            cursor
+                .expect_keyword(Keyword::Where)
+                .map(|token| token.lexeme),
+            Ok("where")
+        );
+        assert_eq!(cursor.eat(TokenType::Ident), None);
+    }
+
+    #[test]
+    fn expect_reports_what_was_found() {
+        let mut cursor = cursor("if\n  ");
+        let error = cursor.expect_punct(Punct::Dot).unwrap_err();
+        assert!(matches!(
+            error,
+            ExpectTokenError::Unexpected(TokenType::Punct(Punct::Dot), token) if token.lexeme == "if"
+        ));
+        assert_eq!(error.span().bytes, ByteSpan::new(0, 2));
+        assert!(cursor.expect(TokenType::Ident).is_err());
+        assert!(cursor.expect_keyword(Keyword::If).is_ok());
+
+        let error = cursor.expect(TokenType::Ident).unwrap_err();
+        assert!(matches!(
+            error,
+            ExpectTokenError::UnexpectedEnd(TokenType::Ident, _)
+        ));
+        assert_eq!(
+            error.span(),
+            Span::new(
+                LineColumn::new(1, 2),
+                LineColumn::new(1, 2),
+                ByteSpan::new(5, 5)
+            )
+        );
+    }
+}
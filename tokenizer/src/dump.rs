@@ -0,0 +1,56 @@
+use std::fmt::{Display, Formatter};
+
+use crate::Token;
+
+/// Formats tokens as a table with one row per token and columns for the token type, the escaped
+/// lexeme, the line/column span and the byte span.
+///
+/// ```text
+/// kind                lexeme  span     bytes
+/// keyword `let`       "let"   1:1-1:4  0..3
+/// identifier          "x"     1:5-1:6  4..5
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct TokenDump<'t, 'a> {
+    tokens: &'t [Token<'a>],
+}
+
+impl<'t, 'a> TokenDump<'t, 'a> {
+    #[must_use]
+    pub fn new(tokens: &'t [Token<'a>]) -> Self {
+        Self { tokens }
+    }
+}
+
+impl Display for TokenDump<'_, '_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let rows = self
+            .tokens
+            .iter()
+            .map(|token| {
+                [
+                    token.token_type.to_string(),
+                    format!("{:?}", token.lexeme),
+                    token.span.to_string(),
+                    token.span.bytes.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let header = ["kind", "lexeme", "span", "bytes"].map(String::from);
+        let mut widths = [0; 4];
+        std::iter::once(&header).chain(&rows).for_each(|row| {
+            widths
+                .iter_mut()
+                .zip(row)
+                .for_each(|(width, cell)| *width = (*width).max(cell.chars().count()));
+        });
+        std::iter::once(&header).chain(&rows).try_for_each(|row| {
+            let [kind, lexeme, span, bytes] = row;
+            writeln!(
+                f,
+                "{kind:<0$}  {lexeme:<1$}  {span:<2$}  {bytes}",
+                widths[0], widths[1], widths[2]
+            )
+        })
+    }
+}
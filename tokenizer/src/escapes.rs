@@ -0,0 +1,321 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum EscapeErrorKind {
+    /// A backslash followed by a char that doesn't start an escape.
+    UnknownEscape(char),
+    /// A backslash at the very end of the input.
+    TrailingBackslash,
+    /// `\x` not followed by two hex digits.
+    InvalidHexEscape,
+    /// `\x` escape above `\x7F` in a string or char literal.
+    HexEscapeOutOfRange(u8),
+    /// `\u` not followed by one to six hex digits between braces.
+    InvalidUnicodeEscape,
+    /// `\u{...}` escape for a surrogate code point.
+    UnicodeEscapeSurrogate(u32),
+    /// `\u{...}` escape above `\u{10FFFF}`.
+    UnicodeEscapeOutOfRange(u32),
+    /// `\u{...}` escape in a byte or byte string literal.
+    UnicodeEscapeInByteLit,
+    /// Non-ASCII char in a byte or byte string literal.
+    NonAsciiInByteLit(char),
+    /// A lexeme passed to [`parse_string_literal`] without matching quotes around its content.
+    NotAStringLiteral,
+}
+
+impl Display for EscapeErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownEscape(c) => write!(f, "Invalid escape \\{c}"),
+            Self::TrailingBackslash => write!(f, "Unfinished escape at end of input"),
+            Self::InvalidHexEscape => write!(f, "\\x must be followed by two hex digits"),
+            Self::HexEscapeOutOfRange(b) => {
+                write!(
+                    f,
+                    "Hex escape \\x{b:02X} is out of range, must be at most \\x7F"
+                )
+            }
+            Self::InvalidUnicodeEscape => {
+                write!(f, "\\u must be followed by one to six hex digits in braces")
+            }
+            Self::UnicodeEscapeSurrogate(u) => {
+                write!(f, "Unicode escape \\u{{{u:X}}} is a surrogate")
+            }
+            Self::UnicodeEscapeOutOfRange(u) => {
+                write!(f, "Unicode escape \\u{{{u:X}}} is out of range")
+            }
+            Self::UnicodeEscapeInByteLit => {
+                write!(f, "Unicode escapes are not allowed in byte literals")
+            }
+            Self::NonAsciiInByteLit(c) => {
+                write!(f, "Non-ASCII char {c:?} in byte literal, use a \\x escape")
+            }
+            Self::NotAStringLiteral => write!(f, "Not a quoted string literal"),
+        }
+    }
+}
+
+/// An invalid escape, with the byte offset of its backslash and its length in bytes within the
+/// string that was parsed.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct ParseEscapesError(EscapeErrorKind, usize, usize);
+
+impl ParseEscapesError {
+    #[must_use]
+    pub fn kind(&self) -> EscapeErrorKind {
+        self.0
+    }
+
+    /// The bytes of the invalid escape, backslash included, within the string passed to
+    /// [`parse_escapes`].
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        self.1..self.1 + self.2
+    }
+}
+
+impl Display for ParseEscapesError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, at pos {}", self.0, self.1)
+    }
+}
+
+impl std::error::Error for ParseEscapesError {}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ParseCharError {
+    Escape(ParseEscapesError),
+    Empty,
+    /// More than one char remained after processing escapes.
+    MultipleChars,
+}
+
+impl From<ParseEscapesError> for ParseCharError {
+    fn from(parse_escapes_error: ParseEscapesError) -> Self {
+        Self::Escape(parse_escapes_error)
+    }
+}
+
+impl Display for ParseCharError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Escape(error) => write!(f, "{error}"),
+            Self::Empty => write!(f, "Empty char literal"),
+            Self::MultipleChars => write!(f, "Char literal must contain exactly one char"),
+        }
+    }
+}
+
+impl std::error::Error for ParseCharError {}
+
+/// Returns the number of `#`s opening the raw string literal at the start of `s`, if there is one.
+pub(crate) fn raw_string_hashes(s: &str) -> Option<usize> {
+    let hashes = s
+        .strip_prefix('r')?
+        .bytes()
+        .take_while(|&b| b == b'#')
+        .count();
+    (s.as_bytes().get(1 + hashes) == Some(&b'"')).then_some(hashes)
+}
+
+/// Returns the value of a string literal given its whole lexeme, quotes included.
+/// Raw strings are returned verbatim; other strings go through [`parse_escapes`].
+/// # Errors
+/// Returns `Err` if there is an invalid escape in a non-raw string, in which case the error's
+/// offset is relative to the content between the quotes. Returns
+/// [`EscapeErrorKind::NotAStringLiteral`] spanning the whole lexeme if it isn't enclosed in
+/// matching quotes, such as a byte string.
+pub fn parse_string_literal(lexeme: &str) -> Result<Cow<'_, str>, ParseEscapesError> {
+    let not_a_literal = || ParseEscapesError(EscapeErrorKind::NotAStringLiteral, 0, lexeme.len());
+    match raw_string_hashes(lexeme) {
+        Some(hashes) => lexeme[hashes + 2..]
+            .strip_suffix(&lexeme[1..=hashes])
+            .and_then(|content| content.strip_suffix('"'))
+            .map(Cow::Borrowed)
+            .ok_or_else(not_a_literal),
+        None => parse_escapes(
+            lexeme
+                .strip_prefix('"')
+                .and_then(|content| content.strip_suffix('"'))
+                .ok_or_else(not_a_literal)?,
+        ),
+    }
+}
+
+/// This function is meant to be used on the content between the terminators of string and character literals.
+/// # Errors
+/// Returns `Err` if there is an invalid escape in `s`.
+pub fn parse_escapes(s: &str) -> Result<Cow<'_, str>, ParseEscapesError> {
+    if !s.contains('\\') {
+        return Ok(Cow::Borrowed(s));
+    }
+    let mut parsed = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('\\') {
+        parsed.push_str(&rest[..i]);
+        let offset = s.len() - rest.len() + i;
+        let (c, len) = parse_escape(&rest[i..], false)
+            .map_err(|(kind, len)| ParseEscapesError(kind, offset, len))?;
+        parsed.extend(c);
+        rest = &rest[i + len..];
+    }
+    parsed.push_str(rest);
+    Ok(Cow::Owned(parsed))
+}
+
+/// Like [`parse_escapes`], but for the content between the terminators of byte string literals,
+/// which may only contain ASCII chars and escapes, including `\x` escapes up to `\xFF`.
+/// # Errors
+/// Returns `Err` if there is an invalid escape or a non-ASCII char in `s`.
+pub fn parse_byte_escapes(s: &str) -> Result<Cow<'_, [u8]>, ParseEscapesError> {
+    let check_ascii = |segment: &str, segment_start: usize| match segment
+        .char_indices()
+        .find(|(_, c)| !c.is_ascii())
+    {
+        Some((i, c)) => Err(ParseEscapesError(
+            EscapeErrorKind::NonAsciiInByteLit(c),
+            segment_start + i,
+            c.len_utf8(),
+        )),
+        None => Ok(()),
+    };
+    if !s.contains('\\') {
+        check_ascii(s, 0)?;
+        return Ok(Cow::Borrowed(s.as_bytes()));
+    }
+    let mut parsed = Vec::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(i) = rest.find('\\') {
+        let offset = s.len() - rest.len() + i;
+        check_ascii(&rest[..i], offset - i)?;
+        parsed.extend_from_slice(&rest.as_bytes()[..i]);
+        let (c, len) = parse_escape(&rest[i..], true)
+            .map_err(|(kind, len)| ParseEscapesError(kind, offset, len))?;
+        // Escapes in byte literals never stand for chars above U+00FF
+        parsed.extend(c.and_then(|c| u8::try_from(c).ok()));
+        rest = &rest[i + len..];
+    }
+    check_ascii(rest, s.len() - rest.len())?;
+    parsed.extend_from_slice(rest.as_bytes());
+    Ok(Cow::Owned(parsed))
+}
+
+/// Parses the escape at the start of `s`, returning the char it stands for, if any, and its length.
+/// In byte literals, `\x` escapes above `\x7F` stand for the char with the same code point.
+fn parse_escape(s: &str, byte: bool) -> Result<(Option<char>, usize), (EscapeErrorKind, usize)> {
+    match s[1..].chars().next() {
+        None => Err((EscapeErrorKind::TrailingBackslash, 1)),
+        // Line continuation
+        Some('\n' | '\r') => Ok((None, if s[1..].starts_with("\r\n") { 3 } else { 2 })),
+        Some('\\') => Ok((Some('\\'), 2)),
+        Some('n') => Ok((Some('\n'), 2)),
+        Some('t') => Ok((Some('\t'), 2)),
+        Some('r') => Ok((Some('\r'), 2)),
+        Some('0') => Ok((Some('\0'), 2)),
+        Some('"') => Ok((Some('"'), 2)),
+        Some('\'') => Ok((Some('\''), 2)),
+        Some('{') => Ok((Some('{'), 2)),
+        Some('}') => Ok((Some('}'), 2)),
+        Some('x') => {
+            let digits = s[2..]
+                .bytes()
+                .take(2)
+                .take_while(u8::is_ascii_hexdigit)
+                .count();
+            if digits < 2 {
+                return Err((EscapeErrorKind::InvalidHexEscape, 2 + digits));
+            }
+            match u8::from_str_radix(&s[2..4], 16) {
+                Ok(b) if b.is_ascii() || byte => Ok((Some(char::from(b)), 4)),
+                Ok(b) => Err((EscapeErrorKind::HexEscapeOutOfRange(b), 4)),
+                Err(_) => Err((EscapeErrorKind::InvalidHexEscape, 4)),
+            }
+        }
+        Some('u') if byte => Err((EscapeErrorKind::UnicodeEscapeInByteLit, 2)),
+        Some('u') => {
+            let Some(braced) = s[2..].strip_prefix('{') else {
+                return Err((EscapeErrorKind::InvalidUnicodeEscape, 2));
+            };
+            let digits = braced.bytes().take_while(u8::is_ascii_hexdigit).count();
+            if !braced[digits..].starts_with('}') || !(1..=6).contains(&digits) {
+                return Err((EscapeErrorKind::InvalidUnicodeEscape, 3 + digits));
+            }
+            let len = 4 + digits;
+            let u = u32::from_str_radix(&braced[..digits], 16)
+                .map_err(|_| (EscapeErrorKind::InvalidUnicodeEscape, len))?;
+            match char::from_u32(u) {
+                Some(c) => Ok((Some(c), len)),
+                None if (0xD800..=0xDFFF).contains(&u) => {
+                    Err((EscapeErrorKind::UnicodeEscapeSurrogate(u), len))
+                }
+                None => Err((EscapeErrorKind::UnicodeEscapeOutOfRange(u), len)),
+            }
+        }
+        Some(c) => Err((EscapeErrorKind::UnknownEscape(c), 1 + c.len_utf8())),
+    }
+}
+
+/// Decodes the content between the terminators of a char literal into the one char it stands for.
+/// # Errors
+/// Returns `Err` if there is an invalid escape in `s`, or if `s` doesn't stand for exactly one char.
+pub fn parse_char(s: &str) -> Result<char, ParseCharError> {
+    let parsed = parse_escapes(s)?;
+    let mut chars = parsed.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        (None, _) => Err(ParseCharError::Empty),
+        (Some(_), Some(_)) => Err(ParseCharError::MultipleChars),
+    }
+}
+
+/// Decodes the content between the terminators of a byte literal into the one byte it stands for.
+/// # Errors
+/// Returns `Err` if there is an invalid escape or a non-ASCII char in `s`, or if `s` doesn't stand
+/// for exactly one byte.
+pub fn parse_byte(s: &str) -> Result<u8, ParseCharError> {
+    match *parse_byte_escapes(s)? {
+        [b] => Ok(b),
+        [] => Err(ParseCharError::Empty),
+        [_, _, ..] => Err(ParseCharError::MultipleChars),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string_literal_without_quotes_is_an_error() {
+        for lexeme in ["", "\"", "b\"x\"", "r\"", "r#\"a\"", "x"] {
+            assert_eq!(
+                parse_string_literal(lexeme).map_err(|error| error.kind()),
+                Err(EscapeErrorKind::NotAStringLiteral),
+                "{lexeme:?}"
+            );
+        }
+        assert_eq!(parse_string_literal("\"\"").as_deref(), Ok(""));
+        assert_eq!(parse_string_literal("r##\"a\"#\"##").as_deref(), Ok("a\"#"));
+    }
+
+    #[test]
+    fn line_continuations() {
+        assert_eq!(parse_escapes("a\\\nb").as_deref(), Ok("ab"));
+        assert_eq!(parse_escapes("a\\\r\nb").as_deref(), Ok("ab"));
+        assert_eq!(parse_escapes("a\\\rb").as_deref(), Ok("ab"));
+        assert_eq!(parse_escapes("a\\\r\n\r\nb").as_deref(), Ok("a\r\nb"));
+    }
+
+    #[test]
+    fn error_ranges_cover_the_escape() {
+        for (content, range) in [("ab\\q", 2..4), ("\\u{110000}x", 0..10), ("日\\x8", 3..6)] {
+            assert_eq!(
+                parse_escapes(content).map_err(|error| error.range()),
+                Err(range),
+                "{content:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,395 @@
+use std::ops::Range;
+
+use crate::{
+    split_tokens, ByteSpan, Interpolation, LexerConfig, LineColumn, ParseTokenError, Span,
+    SplitTokens, Token, TokenType, Trivia,
+};
+
+/// The replacement of the bytes `range` of a source with `replacement`.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TextEdit<'e> {
+    pub range: ByteSpan,
+    pub replacement: &'e str,
+}
+
+impl<'e> TextEdit<'e> {
+    #[must_use]
+    pub fn new(range: ByteSpan, replacement: &'e str) -> Self {
+        Self { range, replacement }
+    }
+
+    /// Returns `source` with this edit applied.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds of `source` or not on char boundaries.
+    #[must_use]
+    pub fn apply(&self, source: &str) -> String {
+        [
+            &source[..self.range.start],
+            self.replacement,
+            &source[self.range.end..],
+        ]
+        .concat()
+    }
+}
+
+/// Which tokens an edit changed: the old tokens at indices `removed` were replaced by the new
+/// tokens at indices `inserted`. Tokens before them are unchanged, and tokens after them only moved.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct TokenDiff {
+    pub removed: Range<usize>,
+    pub inserted: Range<usize>,
+}
+
+/// The result of [`relex`].
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Relexed<'a> {
+    pub tokens: Vec<Token<'a>>,
+    /// The errors met in the re-lexed region only
+    pub errors: Vec<ParseTokenError<'a>>,
+    pub diff: TokenDiff,
+}
+
+/// Updates `old_tokens` for `edit`, lexing `new_source`, the source with the edit applied, only
+/// from shortly before the edit until the new tokens line up with the old ones again.
+///
+/// `old_tokens` must be all the tokens lexed from the old source with `config`. Lexing restarts
+/// one token before the first token touching the edit, since a token can depend on the char after
+/// it, or earlier if that is inside an interpolated string or after text that failed to lex.
+///
+/// # Panics
+///
+/// Panics if `old_tokens` don't fit `new_source` around the edit.
+#[must_use]
+pub fn relex<'a>(
+    old_tokens: &[Token<'_>],
+    edit: TextEdit<'_>,
+    new_source: &'a str,
+    config: LexerConfig,
+) -> Relexed<'a> {
+    let edit_end = edit.range.start + edit.replacement.len();
+    let start = restart_index(old_tokens, edit, new_source);
+
+    let (line_column, offset) = match start.checked_sub(1).map(|i| old_tokens[i].span) {
+        Some(span) => (span.end, span.bytes.end),
+        None => (LineColumn::default(), 0),
+    };
+    let mut tokens = old_tokens[..start]
+        .iter()
+        .map(|token| {
+            Token::new(
+                token.token_type,
+                &new_source[token.span.bytes.range()],
+                token.span,
+            )
+        })
+        .collect::<Vec<_>>();
+    let mut errors = Vec::new();
+    let mut lexer = SplitTokens {
+        config,
+        ..SplitTokens::new(&new_source[offset..], new_source, line_column)
+    };
+
+    // Old tokens are only compared from `old` on, with `modes` as the lexer modes before it
+    let (mut old, mut modes) = (start, Modes::default());
+    let new_start = |token: &Token<'_>| token.span.bytes.start - edit.range.end + edit_end;
+    let resync = loop {
+        let idle = lexer.modes.is_empty();
+        let token = match lexer.next() {
+            Some(Ok(token)) => token,
+            Some(Err(error)) => {
+                errors.push(error);
+                continue;
+            }
+            None => break None,
+        };
+        if idle && token.span.bytes.start >= edit_end {
+            while let Some(old_token) = old_tokens.get(old).filter(|old_token| {
+                old_token.span.bytes.start < edit.range.end
+                    || new_start(old_token) < token.span.bytes.start
+            }) {
+                modes.update(old, old_token.token_type);
+                old += 1;
+            }
+            if let Some(old_token) = old_tokens.get(old).filter(|old_token| {
+                modes.settle(old_token.token_type);
+                modes.is_empty()
+                    && new_start(old_token) == token.span.bytes.start
+                    && old_token.token_type == token.token_type
+                    && old_token.lexeme == token.lexeme
+            }) {
+                break Some((old, old_token.span.start, token.span.start));
+            }
+        }
+        tokens.push(token);
+    };
+
+    let inserted_end = tokens.len();
+    let removed_end = match resync {
+        Some((old, old_anchor, new_anchor)) => {
+            let shift = |line_column: LineColumn| {
+                if line_column.line == old_anchor.line {
+                    LineColumn::new(
+                        new_anchor.line,
+                        line_column.column - old_anchor.column + new_anchor.column,
+                    )
+                } else {
+                    LineColumn::new(
+                        line_column.line - old_anchor.line + new_anchor.line,
+                        line_column.column,
+                    )
+                }
+            };
+            tokens.extend(old_tokens[old..].iter().map(|token| {
+                let bytes = ByteSpan::new(new_start(token), new_start(token) + token.lexeme.len());
+                Token::new(
+                    token.token_type,
+                    &new_source[bytes.range()],
+                    Span::new(shift(token.span.start), shift(token.span.end), bytes),
+                )
+            }));
+            old
+        }
+        None => old_tokens.len(),
+    };
+
+    // Lexing restarted a little early, so the first few tokens may not have changed
+    let unchanged = tokens[start..inserted_end]
+        .iter()
+        .zip(&old_tokens[start..removed_end])
+        .take_while(|(new, old)| {
+            new.token_type == old.token_type && new.lexeme == old.lexeme && new.span == old.span
+        })
+        .count();
+    Relexed {
+        tokens,
+        errors,
+        diff: TokenDiff {
+            removed: start + unchanged..removed_end,
+            inserted: start + unchanged..inserted_end,
+        },
+    }
+}
+
+/// Returns the index of the old token to restart lexing from for `edit`.
+///
+/// Text that failed to lex before that point is kept as is, since error recovery never crosses a
+/// line end except for an unterminated block comment, which runs to the end of the input. The
+/// exception is an unterminated string or char literal, which would end at a quote anywhere after
+/// it, so lexing restarts before it if the edit could add or unescape one.
+fn restart_index(old_tokens: &[Token<'_>], edit: TextEdit<'_>, new_source: &str) -> usize {
+    // Skipped text stands in for a gap, so it doesn't count as the token before the edit
+    let start = old_tokens
+        [..old_tokens.partition_point(|token| token.span.bytes.end < edit.range.start)]
+        .iter()
+        .rposition(|token| token.token_type != TokenType::Trivia(Trivia::Skipped))
+        .unwrap_or(0);
+    // Unescaping a quote also counts, as with `\'` becoming `\x'`
+    let may_terminate = !edit.range.is_empty()
+        || edit.replacement.contains(['"', '\'', '{', '\\'])
+        || new_source[..edit.range.start].ends_with('\\');
+    let mut modes = Modes::default();
+    let mut gap_start = 0;
+    for (i, token) in old_tokens[..start].iter().enumerate() {
+        let gap = &new_source[gap_start..token.span.bytes.start];
+        gap_start = token.span.bytes.end;
+        let skipped = token.token_type == TokenType::Trivia(Trivia::Skipped);
+        if may_terminate && (is_unterminated(gap) || (skipped && is_unterminated(token.lexeme))) {
+            return modes.outermost().unwrap_or(i);
+        }
+        if let Some(opener) = modes.update(i, token.token_type).filter(|_| may_terminate) {
+            return modes
+                .outermost()
+                .map_or(opener, |outermost| outermost.min(opener));
+        }
+    }
+    modes.outermost().unwrap_or(start)
+}
+
+/// Whether `gap`, text between tokens, has an unterminated string or char literal. Lexing it on
+/// its own gives the same errors as in context, since no literal in it found a terminator after it.
+fn is_unterminated(gap: &str) -> bool {
+    !gap.trim_start().is_empty()
+        && split_tokens(gap).any(|result| {
+            matches!(
+                result,
+                Err(ParseTokenError::UnterminatedStrLit(_) | ParseTokenError::UnterminatedChrLit(_))
+            )
+        })
+}
+
+/// The interpolated strings open at some point of a run of old tokens, mirroring the lexer's
+/// modes. Each entry is whether it is in string text rather than an embedded expression, and the
+/// index of the token that opened it.
+#[derive(Debug, Default)]
+struct Modes(Vec<(bool, usize)>);
+
+impl Modes {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The index of the token opening the outermost open interpolated string.
+    fn outermost(&self) -> Option<usize> {
+        self.0.first().map(|&(_, i)| i)
+    }
+
+    /// Closes the innermost string if a token of type `token_type` can't be part of its text,
+    /// which means the lexer gave up on it as unterminated. Returns the index of its opener.
+    fn settle(&mut self, token_type: TokenType) -> Option<usize> {
+        let &(true, opener) = self.0.last()? else {
+            return None;
+        };
+        if matches!(
+            token_type,
+            TokenType::Interpolation(
+                Interpolation::Fragment | Interpolation::ExprStart | Interpolation::StrEnd
+            ) | TokenType::Trivia(Trivia::Skipped)
+        ) {
+            return None;
+        }
+        self.0.pop();
+        Some(opener)
+    }
+
+    /// Moves past the token at index `i`, returning the index of the opener of an unterminated
+    /// string it closed, as with [`Modes::settle`].
+    fn update(&mut self, i: usize, token_type: TokenType) -> Option<usize> {
+        let unterminated = self.settle(token_type);
+        match token_type {
+            TokenType::Interpolation(Interpolation::StrStart) => self.0.push((true, i)),
+            TokenType::Interpolation(Interpolation::ExprStart) => self.0.push((false, i)),
+            TokenType::Interpolation(Interpolation::ExprEnd | Interpolation::StrEnd) => {
+                self.0.pop();
+            }
+            _ => {}
+        }
+        unterminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str =
+        "let x := \"a {b + \"c{d}\"} e\" + 3424.5;\r\n// hi\n$ fn Foo(y) { ret y ** 2 \
+        /* c */ }\nlet s := r#\"raw\"# + 'c' + b\"x\";\n日本 := 0x1F_u8;\n";
+
+    fn tokens(source: &str, config: LexerConfig) -> Vec<Token<'_>> {
+        SplitTokens::with_config(source, config).flatten().collect()
+    }
+
+    /// Applies pseudo-random edits, checking each re-lex against lexing the edited source whole.
+    fn check_random_edits(config: LexerConfig, seed: u64, edits: usize) {
+        const PIECES: &[&str] = &[
+            "", "x", " ", "\"", "{", "}", "\n", "5", ".", "/*", "*/", "//", "'", "r#", "日",
+            "\r\n", "Q", "+", "$", "\\", "b\"", "a\"b{", "'q",
+        ];
+        let mut state = seed;
+        let mut random = |n: usize| {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            usize::try_from(state >> 33_u32).unwrap() % n
+        };
+        let mut source = SOURCE.to_owned();
+        for _ in 0..edits {
+            let boundaries = (0..=source.len())
+                .filter(|&i| source.is_char_boundary(i))
+                .collect::<Vec<_>>();
+            let start = boundaries[random(boundaries.len())];
+            let end = boundaries[random(boundaries.len())].clamp(start, start + 6);
+            let end = (start..=end)
+                .rev()
+                .find(|&i| source.is_char_boundary(i))
+                .unwrap();
+            let edit = TextEdit::new(ByteSpan::new(start, end), PIECES[random(PIECES.len())]);
+            let old_tokens = tokens(&source, config);
+            let new_source = edit.apply(&source);
+            let relexed = relex(&old_tokens, edit, &new_source, config);
+            assert_eq!(
+                relexed.tokens,
+                tokens(&new_source, config),
+                "{source:?} {edit:?}"
+            );
+            let TokenDiff { removed, inserted } = relexed.diff;
+            assert_eq!(removed.start, inserted.start);
+            assert_eq!(
+                old_tokens[..removed.start],
+                relexed.tokens[..inserted.start]
+            );
+            assert_eq!(
+                old_tokens.len() - removed.end,
+                relexed.tokens.len() - inserted.end
+            );
+            source = if (20..400).contains(&new_source.len()) {
+                new_source
+            } else {
+                SOURCE.to_owned()
+            };
+        }
+    }
+
+    #[test]
+    fn random_edits_match_full_lex() {
+        for seed in [1, 2, 3] {
+            check_random_edits(LexerConfig::new(), seed, 2000);
+            check_random_edits(LexerConfig::new().trivia(true), seed, 2000);
+            check_random_edits(LexerConfig::new().comments(false), seed, 2000);
+        }
+    }
+
+    #[test]
+    fn earlier_errors_and_comments_dont_restart_from_the_top() {
+        let source = format!("$ // hi\n{}", "let x := 1;\n".repeat(100));
+        let config = LexerConfig::new().comments(false);
+        let old_tokens = tokens(&source, config);
+        let edit = TextEdit::new(ByteSpan::new(source.len() - 2, source.len() - 2), "2");
+        let new_source = edit.apply(&source);
+        assert_eq!(
+            restart_index(&old_tokens, edit, &new_source),
+            old_tokens.len() - 3
+        );
+    }
+
+    #[test]
+    fn unterminated_literals_restart_when_the_edit_may_close_them() {
+        let source = format!("let s := \"a\n{}", "let x := 1;\n".repeat(10));
+        let old_tokens = tokens(&source, LexerConfig::new());
+        let at_end = ByteSpan::new(source.len(), source.len());
+        assert_eq!(
+            restart_index(&old_tokens, TextEdit::new(at_end, "x"), &source),
+            old_tokens.len() - 1
+        );
+        assert_eq!(
+            restart_index(&old_tokens, TextEdit::new(at_end, "\""), &source),
+            3
+        );
+    }
+
+    #[test]
+    fn edits_next_to_escapes_and_skipped_text() {
+        for (source, edit, config) in [
+            // Unescapes the last quote, closing the char literal on the first line
+            (
+                "a.'QF\na\"b{\n8x5;$\\'\n\\{",
+                TextEdit::new(ByteSpan::new(17, 17), "x"),
+                LexerConfig::new(),
+            ),
+            // Turns the `r` before the skipped `##` into a raw string prefix
+            (
+                "r##:= 5;\n",
+                TextEdit::new(ByteSpan::new(3, 5), "\""),
+                LexerConfig::new().trivia(true),
+            ),
+        ] {
+            let new_source = edit.apply(source);
+            assert_eq!(
+                relex(&tokens(source, config), edit, &new_source, config).tokens,
+                tokens(&new_source, config)
+            );
+        }
+    }
+}
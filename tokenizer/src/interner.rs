@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::iter::FusedIterator;
+use std::sync::Arc;
+
+use crate::{nfc, Keyword, ParseTokenError, SplitTokens, Token, TokenType};
+
+/// A name interned in an [`Interner`], which compares and hashes in O(1).
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// The position of this symbol in its interner, starting from 0.
+    #[must_use]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Keywords are interned first, in the order of [`Keyword::ALL`], so every interner gives them
+/// the same symbols.
+impl From<Keyword> for Symbol {
+    fn from(keyword: Keyword) -> Self {
+        Keyword::ALL
+            .iter()
+            .zip(0..)
+            .find_map(|(&other, index)| (other == keyword).then_some(Self(index)))
+            .expect("every keyword is in Keyword::ALL")
+    }
+}
+
+/// Maps names to [`Symbol`]s and back. Names are interned in NFC, so canonically equivalent
+/// spellings get the same symbol.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Interner {
+    symbols: HashMap<Arc<str>, Symbol>,
+    names: Vec<Arc<str>>,
+}
+
+impl Interner {
+    #[must_use]
+    pub fn new() -> Self {
+        let mut interner = Self {
+            symbols: HashMap::new(),
+            names: Vec::new(),
+        };
+        for keyword in Keyword::ALL {
+            interner.intern(&keyword.to_string());
+        }
+        interner
+    }
+
+    /// Returns the symbol for `name`, interning it if it is new.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` names are interned.
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        let name = nfc(name);
+        if let Some(&symbol) = self.symbols.get(&*name) {
+            return symbol;
+        }
+        let symbol = Symbol(u32::try_from(self.names.len()).expect("too many interned names"));
+        let name = Arc::<str>::from(name);
+        self.symbols.insert(Arc::clone(&name), symbol);
+        self.names.push(name);
+        symbol
+    }
+
+    /// Returns the symbol for `name` if it has been interned.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.symbols.get(&*nfc(name)).copied()
+    }
+
+    /// Returns the name `symbol` stands for, or `None` if it is from another interner.
+    #[must_use]
+    pub fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.names.get(symbol.index()).map(|name| &**name)
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+impl Default for Interner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Tokens paired with the [`Symbol`] of their name, for keywords and identifiers. Created by
+/// [`SplitTokens::interned`].
+#[derive(Debug)]
+pub struct InternedTokens<'a, 'i> {
+    tokens: SplitTokens<'a>,
+    interner: &'i mut Interner,
+}
+
+impl<'a> SplitTokens<'a> {
+    /// Interns the names of keywords and identifiers as they are lexed.
+    #[must_use]
+    pub fn interned(self, interner: &mut Interner) -> InternedTokens<'a, '_> {
+        InternedTokens {
+            tokens: self,
+            interner,
+        }
+    }
+}
+
+impl<'a> Iterator for InternedTokens<'a, '_> {
+    type Item = Result<(Token<'a>, Option<Symbol>), ParseTokenError<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.tokens.next()?.map(|token| {
+            let symbol = match token.token_type {
+                TokenType::Keyword(keyword) | TokenType::ContextualKeyword(keyword) => {
+                    Some(keyword.into())
+                }
+                TokenType::Ident | TokenType::ProperIdent => {
+                    Some(self.interner.intern(token.lexeme))
+                }
+                _ => None,
+            };
+            (token, symbol)
+        }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.tokens.size_hint()
+    }
+}
+
+impl FusedIterator for InternedTokens<'_, '_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::split_tokens;
+
+    #[test]
+    fn keywords_resolve_to_their_names() {
+        let interner = Interner::new();
+        for &keyword in Keyword::ALL {
+            assert_eq!(
+                interner.resolve(keyword.into()),
+                Some(&*keyword.to_string())
+            );
+        }
+        assert_eq!(interner.len(), Keyword::ALL.len());
+    }
+
+    #[test]
+    fn names_are_interned_once_in_nfc() {
+        let mut interner = Interner::new();
+        let symbol = interner.intern("caf\u{e9}");
+        assert_eq!(interner.intern("cafe\u{301}"), symbol);
+        assert_eq!(interner.get("cafe\u{301}"), Some(symbol));
+        assert_eq!(interner.resolve(symbol), Some("caf\u{e9}"));
+        assert_eq!(interner.get("tea"), None);
+        assert_eq!(interner.len(), Keyword::ALL.len() + 1);
+    }
+
+    #[test]
+    fn interned_tokens_share_symbols() {
+        let mut interner = Interner::new();
+        let symbols = split_tokens("x.type := x + 1")
+            .interned(&mut interner)
+            .map(|result| result.unwrap().1)
+            .collect::<Vec<_>>();
+        assert_eq!(symbols[0], symbols[4]);
+        assert_eq!(symbols[2], Some(Keyword::Type.into()));
+        assert_eq!(symbols[1], None);
+        assert_eq!(interner.get("type"), symbols[2]);
+    }
+}
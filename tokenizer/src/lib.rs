@@ -7,13 +7,11 @@
     clippy::str_to_string,
     clippy::string_add,
     clippy::string_lit_chars_any,
-    clippy::string_to_string,
     clippy::suspicious_xor_used_as_pow,
     clippy::tests_outside_test_module,
     clippy::todo,
     clippy::try_err,
     clippy::undocumented_unsafe_blocks,
-    clippy::todo,
     clippy::unimplemented,
     clippy::unnecessary_self_imports,
     clippy::unneeded_field_pattern,
@@ -28,49 +26,143 @@
     unused_must_use
 )]
 
+pub use crate::case::UncasedIdents;
+use crate::case::{is_caps, is_uncased};
+pub use crate::columns::{ColumnUnit, LineIndex};
+pub use crate::config::LexerConfig;
+pub use crate::cursor::{Checkpoint, ExpectTokenError, TokenCursor};
+pub use crate::dump::TokenDump;
+use crate::escapes::raw_string_hashes;
+pub use crate::escapes::{
+    parse_byte, parse_byte_escapes, parse_char, parse_escapes, parse_string_literal,
+    EscapeErrorKind, ParseCharError, ParseEscapesError,
+};
+pub use crate::incremental::{relex, Relexed, TextEdit, TokenDiff};
+pub use crate::interner::{InternedTokens, Interner, Symbol};
+use crate::numeric::{is_digit_or_separator, parse_suffix, split_int};
+pub use crate::numeric::{parse_decimal, parse_int, DecimalValue, IntValue, ParseNumberError};
+pub use crate::owned::{OwnedParseTokenError, OwnedToken};
 pub use crate::types::{
-    Comment, Delimiter, Keyword, LineColumn, Literal, Punct, Span, Token, TokenType,
+    nfc, ByteSpan, Comment, Delimiter, Interpolation, Keyword, LineColumn, Literal, ParseKindError,
+    Punct, Span, Suffix, SuffixKind, Token, TokenType, Trivia,
 };
-use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 use std::iter::FusedIterator;
+use unicode_ident::{is_xid_continue, is_xid_start};
 
+mod case;
+mod columns;
+mod config;
+mod cursor;
+mod dump;
+mod escapes;
+mod incremental;
+mod interner;
+mod numeric;
+mod owned;
 mod types;
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub enum ParseTokenError<'a> {
-    InvalidChar(char, &'a str),
-    CapsInImproperIdent(&'a str, usize),
-    UnderscoreInProper(&'a str, usize),
-    UnterminatedStrLit,
-    UnterminatedChrLit,
-    InvalidEscape(char),
+    InvalidChar(char, &'a str, Span),
+    /// A non-ASCII char that can't appear in an identifier, either inside one or where one starts
+    InvalidIdentChar(char, Span),
+    CapsInImproperIdent(&'a str, usize, Span),
+    UnderscoreInProper(&'a str, usize, Span),
+    UnterminatedStrLit(Span),
+    UnterminatedChrLit(Span),
+    InvalidEscape(EscapeErrorKind, Span),
+    InvalidDigit(char, u32, Span),
+    MissingDigits(&'a str, Span),
+    InvalidSuffix(&'a str, Span),
+    UnterminatedBlockComment(Span),
+    EmptyChrLit(Span),
+    MultiCharChrLit(&'a str, Span),
+    EmptyByteLit(Span),
+    MultiCharByteLit(&'a str, Span),
+}
+
+impl ParseTokenError<'_> {
+    /// The span of the offending characters.
+    #[must_use]
+    pub fn span(&self) -> Span {
+        match self {
+            Self::InvalidChar(_, _, span)
+            | Self::InvalidIdentChar(_, span)
+            | Self::CapsInImproperIdent(_, _, span)
+            | Self::UnderscoreInProper(_, _, span)
+            | Self::UnterminatedStrLit(span)
+            | Self::UnterminatedChrLit(span)
+            | Self::InvalidEscape(_, span)
+            | Self::InvalidDigit(_, _, span)
+            | Self::MissingDigits(_, span)
+            | Self::InvalidSuffix(_, span)
+            | Self::UnterminatedBlockComment(span)
+            | Self::EmptyChrLit(span)
+            | Self::MultiCharChrLit(_, span)
+            | Self::EmptyByteLit(span)
+            | Self::MultiCharByteLit(_, span) => *span,
+        }
+    }
 }
 
-impl<'a> Display for ParseTokenError<'a> {
+impl Display for ParseTokenError<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.span().start)?;
         match self {
-            Self::InvalidChar(c, _) => write!(f, "Invalid char: {c}"),
-            Self::CapsInImproperIdent(s, i) => {
+            Self::InvalidChar(c, _, _) => write!(f, "Invalid char: {c}"),
+            Self::InvalidIdentChar(c, _) => write!(
+                f,
+                "Char {c:?} (U+{:04X}) is not allowed in identifiers",
+                u32::from(*c)
+            ),
+            Self::CapsInImproperIdent(s, i, _) => {
                 write!(f, "Caps in improper identifier, {s:?}, at pos {i}")
             }
-            Self::UnderscoreInProper(s, i) => {
+            Self::UnderscoreInProper(s, i, _) => {
                 write!(f, "Underscore in proper identifier, {s:?}, at pos {i}")
             }
-            Self::UnterminatedStrLit => write!(f, "No string terminator found!"),
-            Self::UnterminatedChrLit => write!(f, "No char terminator found!"),
-            Self::InvalidEscape(c) => write!(f, "Invalid escape \\{c}"),
+            Self::UnterminatedStrLit(_) => write!(f, "No string terminator found!"),
+            Self::UnterminatedChrLit(_) => write!(f, "No char terminator found!"),
+            Self::InvalidEscape(kind, _) => write!(f, "{kind}"),
+            Self::InvalidDigit(c, radix, _) => {
+                write!(f, "Invalid digit for a base {radix} literal: {c}")
+            }
+            Self::MissingDigits(s, _) => write!(f, "No digits in integer literal {s:?}"),
+            Self::InvalidSuffix(s, _) => write!(f, "Invalid numeric literal suffix {s:?}"),
+            Self::UnterminatedBlockComment(_) => write!(f, "No block comment terminator found!"),
+            Self::EmptyChrLit(_) => write!(f, "Empty char literal"),
+            Self::MultiCharChrLit(s, _) => {
+                write!(f, "Char literal {s} must contain exactly one char")
+            }
+            Self::EmptyByteLit(_) => write!(f, "Empty byte literal"),
+            Self::MultiCharByteLit(s, _) => {
+                write!(f, "Byte literal {s} must contain exactly one byte")
+            }
         }
     }
 }
 
-impl<'a> std::error::Error for ParseTokenError<'a> {}
+impl std::error::Error for ParseTokenError<'_> {}
+
+/// Lexer state for interpolated strings.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+enum Mode {
+    /// Inside the text of an interpolated string, with the span of its opening quote
+    String(Span),
+    /// Inside an embedded expression, with the number of `{`s it has left open
+    Expr(usize),
+}
 
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
 pub struct SplitTokens<'a> {
     remainder: &'a str,
     original: &'a str,
     line_column: LineColumn,
+    config: LexerConfig,
+    modes: Vec<Mode>,
+    /// The text skipped over by the last error, yielded next when trivia is enabled
+    skipped: Option<Token<'a>>,
 }
 
 impl<'a> SplitTokens<'a> {
@@ -80,9 +172,25 @@ impl<'a> SplitTokens<'a> {
             remainder,
             original,
             line_column,
+            config: LexerConfig::new(),
+            modes: Vec::new(),
+            skipped: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_config(string: &'a str, config: LexerConfig) -> Self {
+        Self {
+            config,
+            ..Self::new(string, string, LineColumn::new(0, 0))
         }
     }
 
+    #[must_use]
+    pub fn config(&self) -> LexerConfig {
+        self.config
+    }
+
     #[must_use]
     pub fn remainder(&self) -> &str {
         self.remainder
@@ -97,6 +205,134 @@ impl<'a> SplitTokens<'a> {
     pub fn line_column(&self) -> LineColumn {
         self.line_column
     }
+
+    /// Byte offset of the remainder within the original input.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.original.len() - self.remainder.len()
+    }
+
+    /// Lexes the rest of the input, collecting every token and every error instead of stopping
+    /// at the first error.
+    #[must_use]
+    pub fn tokens_and_errors(self) -> (Vec<Token<'a>>, Vec<ParseTokenError<'a>>) {
+        let (mut tokens, mut errors) = (Vec::new(), Vec::new());
+        self.for_each(|result| match result {
+            Ok(token) => tokens.push(token),
+            Err(error) => errors.push(error),
+        });
+        (tokens, errors)
+    }
+
+    /// Returns the span of the bytes `start..end` of the remainder.
+    fn sub_span(&self, start: usize, end: usize) -> Span {
+        let start_line_column = self.line_column.advance(&self.remainder[..start]);
+        Span::new(
+            start_line_column,
+            start_line_column.advance(&self.remainder[start..end]),
+            ByteSpan::new(self.offset() + start, self.offset() + end),
+        )
+    }
+
+    /// Returns the end of the run of base `radix` digits and `_` starting at byte `start` of the
+    /// remainder.
+    fn digits_end(&self, start: usize, radix: u32) -> usize {
+        self.remainder[start..]
+            .find(|c: char| !is_digit_or_separator(c, radix))
+            .map_or(self.remainder.len(), |i| start + i)
+    }
+
+    /// Returns the end of the word starting at byte `start` of the remainder that a numeric literal
+    /// would take as its suffix.
+    fn suffix_end(&self, start: usize) -> usize {
+        self.remainder[start..]
+            .find(|c: char| !is_xid_continue(c))
+            .map_or(self.remainder.len(), |i| start + i)
+    }
+
+    /// Converts an escape error in content starting at byte `content_start` of the remainder.
+    fn escape_error(&self, error: &ParseEscapesError, content_start: usize) -> ParseTokenError<'a> {
+        let range = error.range();
+        ParseTokenError::InvalidEscape(
+            error.kind(),
+            self.sub_span(content_start + range.start, content_start + range.end),
+        )
+    }
+
+    /// Converts an error in a char or byte literal spanning the first `len` bytes of the remainder,
+    /// whose content starts at byte `content_start`. Byte literals are told apart by their `b`.
+    fn char_error(
+        &self,
+        error: ParseCharError,
+        len: usize,
+        content_start: usize,
+    ) -> ParseTokenError<'a> {
+        let (lexeme, span) = (&self.remainder[..len], self.sub_span(0, len));
+        match (error, lexeme.starts_with('b')) {
+            (ParseCharError::Escape(error), _) => self.escape_error(&error, content_start),
+            (ParseCharError::Empty, false) => ParseTokenError::EmptyChrLit(span),
+            (ParseCharError::Empty, true) => ParseTokenError::EmptyByteLit(span),
+            (ParseCharError::MultipleChars, false) => {
+                ParseTokenError::MultiCharChrLit(lexeme, span)
+            }
+            (ParseCharError::MultipleChars, true) => {
+                ParseTokenError::MultiCharByteLit(lexeme, span)
+            }
+        }
+    }
+
+    /// Yields the first `len` bytes of the remainder as a token.
+    fn emit(&mut self, token_type: TokenType, len: usize) -> Token<'a> {
+        let (lexeme, remainder) = self.remainder.split_at(len);
+        let token = Token::new_auto_span(token_type, lexeme, self.line_column, self.offset());
+        (self.line_column, self.remainder) = (token.span.end, remainder);
+        token
+    }
+
+    /// Lexes the text of an interpolated string whose opening quote is at `opener`.
+    fn next_in_string(&mut self, opener: Span) -> Result<Token<'a>, ParseTokenError<'a>> {
+        match self.remainder.as_bytes().first() {
+            Some(b'"') => {
+                self.modes.pop();
+                Ok(self.emit(TokenType::Interpolation(Interpolation::StrEnd), 1))
+            }
+            Some(b'{') => {
+                self.modes.push(Mode::Expr(0));
+                Ok(self.emit(TokenType::Interpolation(Interpolation::ExprStart), 1))
+            }
+            Some(_) => {
+                let Some(end) = fragment_end(self.remainder) else {
+                    self.modes.pop();
+                    // Resume lexing on the next line
+                    let line_end = line_end(self.remainder);
+                    return self.recover(ParseTokenError::UnterminatedStrLit(opener), line_end);
+                };
+                if let Err(error) = parse_escapes(&self.remainder[..end]) {
+                    return self.recover(self.escape_error(&error, 0), end);
+                }
+                Ok(self.emit(TokenType::Interpolation(Interpolation::Fragment), end))
+            }
+            None => {
+                self.modes.pop();
+                Err(ParseTokenError::UnterminatedStrLit(opener))
+            }
+        }
+    }
+
+    /// Skips the first `len` bytes of the remainder so that lexing can resume after `error`.
+    fn recover(
+        &mut self,
+        error: ParseTokenError<'a>,
+        len: usize,
+    ) -> Result<Token<'a>, ParseTokenError<'a>> {
+        if self.config.trivia && len > 0 {
+            self.skipped = Some(self.emit(TokenType::Trivia(Trivia::Skipped), len));
+        } else {
+            let (skipped, remainder) = self.remainder.split_at(len);
+            (self.line_column, self.remainder) = (self.line_column.advance(skipped), remainder);
+        }
+        Err(error)
+    }
 }
 
 macro_rules! sp {
@@ -123,6 +359,7 @@ macro_rules! st {
                 Span::new(
                     $self.line_column,
                     LineColumn::new($self.line_column.line, $self.line_column.column + 1),
+                    ByteSpan::new($self.offset(), $self.offset() + len),
                 ),
             ),
             remainder,
@@ -140,6 +377,7 @@ macro_rules! st {
                 Span::new(
                     $self.line_column,
                     LineColumn::new($self.line_column.line, $self.line_column.column + 2),
+                    ByteSpan::new($self.offset(), $self.offset() + len),
                 ),
             ),
             remainder,
@@ -157,6 +395,7 @@ macro_rules! st {
                 Span::new(
                     $self.line_column,
                     LineColumn::new($self.line_column.line, $self.line_column.column + 3),
+                    ByteSpan::new($self.offset(), $self.offset() + len),
                 ),
             ),
             remainder,
@@ -186,124 +425,309 @@ macro_rules! find_unescaped {
     }};
 }
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-pub struct ParseEscapesError(char);
+/// Returns the end of the identifier at the start of `s`, which must start with an `XID_Start` char
+/// or `_`.
+fn ident_end(s: &str) -> usize {
+    s.char_indices()
+        .skip(1)
+        .find(|&(_, c)| !is_xid_continue(c))
+        .map_or(s.len(), |(i, _)| i)
+}
 
-impl<'a> From<ParseEscapesError> for ParseTokenError<'a> {
-    fn from(parse_escapes_error: ParseEscapesError) -> Self {
-        Self::InvalidEscape(parse_escapes_error.0)
-    }
+/// Returns the end of the line at the start of `s`, before its `\n`, `\r\n` or `\r`.
+fn line_end(s: &str) -> usize {
+    s.find(['\n', '\r']).unwrap_or(s.len())
 }
 
-/// This function is meant to be used on the content between the terminators of string and character literals.
-/// # Errors
-/// Returns `Err` if there is an invalid escape in `s`.
-pub fn parse_escapes(s: &str) -> Result<Cow<str>, ParseEscapesError> {
-    if !s.contains('\\') {
-        return Ok(Cow::Borrowed(s));
-    }
-    let mut escaped = false;
-    s.chars()
-        .filter_map(|c| match (c, escaped) {
-            ('\\', false) => {
-                escaped = true;
-                None
-            }
-            (cc, true) => {
-                escaped = false;
-                match cc {
-                    '\\' => Some(Ok('\\')),
-                    '\n' => None,
-                    'n' => Some(Ok('\n')),
-                    't' => Some(Ok('\t')),
-                    '0' => Some(Ok('\0')),
-                    '"' => Some(Ok('"')),
-                    '\'' => Some(Ok('\'')),
-                    ccc => Some(Err(ParseEscapesError(ccc))),
+/// Returns the end of the fragment of interpolated string text at the start of `s`, which is the
+/// first unescaped `"` or `{`. The braces of a `\u{...}` escape don't count.
+fn fragment_end(s: &str) -> Option<usize> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while let Some(&b) = bytes.get(i) {
+        match b {
+            b'"' | b'{' => return Some(i),
+            b'\\' if bytes[i + 1..].starts_with(b"u{") => {
+                // Skip through the closing brace, unless the string ends first
+                i += 3;
+                if let Some(close) = bytes[i..].iter().position(|&b| matches!(b, b'}' | b'"')) {
+                    if bytes[i + close] == b'}' {
+                        i += close + 1;
+                    }
                 }
             }
-            (cc, false) => Some(Ok(cc)),
-        })
-        .collect()
+            b'\\' => i += 2,
+            _ => i += 1,
+        }
+    }
+    None
 }
 
-impl<'a> Iterator for SplitTokens<'a> {
-    type Item = Result<Token<'a>, ParseTokenError<'a>>;
+impl<'a> SplitTokens<'a> {
     #[allow(clippy::too_many_lines)]
-    fn next(&mut self) -> Option<Self::Item> {
-        self.remainder = self.remainder.trim_matches(|c: char| {
-            if c == '\n' {
-                self.line_column.column = 0;
-                self.line_column.line += 1;
-            } else {
-                self.line_column.column += usize::from(c.is_whitespace());
+    fn next_token(&mut self) -> Option<Result<Token<'a>, ParseTokenError<'a>>> {
+        if let Some(&Mode::String(opener)) = self.modes.last() {
+            return Some(self.next_in_string(opener));
+        }
+        if !self.config.trivia {
+            let (whitespace, remainder) = self.remainder.split_at(
+                self.remainder
+                    .find(|c: char| !c.is_whitespace())
+                    .unwrap_or(self.remainder.len()),
+            );
+            (self.line_column, self.remainder) = (self.line_column.advance(whitespace), remainder);
+        }
+        // Embedded expressions of interpolated strings
+        match (self.remainder.as_bytes().first(), self.modes.last_mut()) {
+            (None, Some(_)) => {
+                // Report the innermost unterminated string, leaving the outer ones for later
+                while let Some(mode) = self.modes.pop() {
+                    if let Mode::String(opener) = mode {
+                        return Some(Err(ParseTokenError::UnterminatedStrLit(opener)));
+                    }
+                }
+                return None;
             }
-            c.is_whitespace()
-        });
+            (Some(b'{'), Some(Mode::Expr(depth))) => *depth += 1,
+            (Some(b'}'), Some(Mode::Expr(0))) => {
+                self.modes.pop();
+                return Some(Ok(
+                    self.emit(TokenType::Interpolation(Interpolation::ExprEnd), 1)
+                ));
+            }
+            (Some(b'}'), Some(Mode::Expr(depth))) => *depth -= 1,
+            _ => {}
+        }
 
         let mut chars = self.remainder.chars();
         Some(
             match (chars.next()?, chars.next().map(|c| (c, chars.next()))) {
-                // Decimal Integer Literals
-                sp!('0'..='9') | sp!('+' | '-', '0'..='9') => {
-                    let mut char_indices = self.remainder.char_indices();
-                    let _ = char_indices.next(); // Skip 1
-                    let (i, lit) = match (
-                        char_indices.find(|&(_, c)| !(c.is_ascii_digit() | (c == '_'))),
-                        char_indices.next(),
-                    ) {
-                        (Some((_, '.')), Some((_, '0'..='9'))) => (
-                            char_indices
-                                .find(|&(_, c)| !(c.is_ascii_digit() | (c == '_')))
-                                .map_or(self.remainder.len(), |(i, _)| i),
-                            Literal::NonInt,
+                // Trivia, only reached when enabled
+                sp!('\n' | '\r') => {
+                    let len = if self.remainder.starts_with("\r\n") {
+                        2
+                    } else {
+                        1
+                    };
+                    let (token, remainder) = self.remainder.split_at(len);
+                    Ok((
+                        Token::new_auto_span(
+                            TokenType::Trivia(Trivia::Newline),
+                            token,
+                            self.line_column,
+                            self.offset(),
+                        ),
+                        remainder,
+                    ))
+                }
+                (c, _) if c.is_whitespace() => {
+                    let (token, remainder) = self.remainder.split_at(
+                        self.remainder
+                            .find(|c: char| !c.is_whitespace() || matches!(c, '\n' | '\r'))
+                            .unwrap_or(self.remainder.len()),
+                    );
+                    Ok((
+                        Token::new_auto_span(
+                            TokenType::Trivia(Trivia::Whitespace),
+                            token,
+                            self.line_column,
+                            self.offset(),
                         ),
-                        (Some((i, _)), _) => (i, Literal::DecInt),
-                        (None, _) => (self.remainder.len(), Literal::DecInt),
+                        remainder,
+                    ))
+                }
+                // Hexadecimal, Octal and Binary Integer Literals
+                sp!('0', 'x' | 'o' | 'b') | sp!('+' | '-', '0', 'x' | 'o' | 'b') => {
+                    let (_, radix, digits) = split_int(self.remainder);
+                    let digits_start = self.remainder.len() - digits.len();
+                    let digits_end = self.digits_end(digits_start, radix);
+                    let (token, remainder) = self.remainder.split_at(self.suffix_end(digits_end));
+                    let word = &token[digits_end..];
+                    let suffix =
+                        parse_suffix(word).filter(|suffix| suffix.kind != SuffixKind::Float);
+                    let has_digits =
+                        token[digits_start..digits_end].contains(|c: char| c.is_digit(radix));
+                    if let Some(c) = word
+                        .chars()
+                        .next()
+                        .filter(|c| c.is_ascii_digit() || (!has_digits && suffix.is_none()))
+                    {
+                        return Some(self.recover(
+                            ParseTokenError::InvalidDigit(
+                                c,
+                                radix,
+                                self.sub_span(digits_end, digits_end + c.len_utf8()),
+                            ),
+                            token.len(),
+                        ));
+                    }
+                    if !has_digits {
+                        return Some(self.recover(
+                            ParseTokenError::MissingDigits(token, self.sub_span(0, token.len())),
+                            token.len(),
+                        ));
+                    }
+                    if suffix.is_none() && !word.is_empty() {
+                        return Some(self.recover(
+                            ParseTokenError::InvalidSuffix(
+                                word,
+                                self.sub_span(digits_end, token.len()),
+                            ),
+                            token.len(),
+                        ));
+                    }
+                    let lit = match radix {
+                        16 => Literal::HexInt(suffix),
+                        8 => Literal::OctInt(suffix),
+                        _ => Literal::BinInt(suffix),
                     };
-                    let (token, remainder) = self.remainder.split_at(i);
                     Ok((
-                        Token::new_auto_span(TokenType::Literal(lit), token, self.line_column),
+                        Token::new_auto_span(
+                            TokenType::Literal(lit),
+                            token,
+                            self.line_column,
+                            self.offset(),
+                        ),
+                        remainder,
+                    ))
+                }
+                // Decimal Integer and Non-Integer Literals
+                sp!('0'..='9') | sp!('+' | '-', '0'..='9') => {
+                    let mut digits_end = self.digits_end(1, 10);
+                    let mut non_int = false;
+                    if let [b'.', b'0'..=b'9', ..] = &self.remainder.as_bytes()[digits_end..] {
+                        (digits_end, non_int) = (self.digits_end(digits_end + 1, 10), true);
+                    }
+                    if let [b'e' | b'E', b'0'..=b'9', ..]
+                    | [b'e' | b'E', b'+' | b'-', b'0'..=b'9', ..] =
+                        &self.remainder.as_bytes()[digits_end..]
+                    {
+                        (digits_end, non_int) = (self.digits_end(digits_end + 2, 10), true);
+                    }
+                    let (token, remainder) = self.remainder.split_at(self.suffix_end(digits_end));
+                    let word = &token[digits_end..];
+                    let suffix = parse_suffix(word)
+                        .filter(|suffix| !non_int || (suffix.kind == SuffixKind::Float));
+                    if suffix.is_none() && !word.is_empty() {
+                        return Some(self.recover(
+                            ParseTokenError::InvalidSuffix(
+                                word,
+                                self.sub_span(digits_end, token.len()),
+                            ),
+                            token.len(),
+                        ));
+                    }
+                    // A float suffix makes an integer like `5f32` a float literal
+                    let lit = if non_int || suffix.is_some_and(|s| s.kind == SuffixKind::Float) {
+                        Literal::NonInt(suffix)
+                    } else {
+                        Literal::DecInt(suffix)
+                    };
+                    Ok((
+                        Token::new_auto_span(
+                            TokenType::Literal(lit),
+                            token,
+                            self.line_column,
+                            self.offset(),
+                        ),
                         remainder,
                     ))
                 }
                 // Comments
                 sp!('/', '/', '/') => {
-                    let (token, remainder) = self
-                        .remainder
-                        .split_at(self.remainder.find('\n').unwrap_or(self.remainder.len()));
+                    let (token, remainder) = self.remainder.split_at(line_end(self.remainder));
                     Ok((
                         Token::new_auto_span(
                             TokenType::Comment(Comment::DocComment),
                             token,
                             self.line_column,
+                            self.offset(),
+                        ),
+                        remainder,
+                    ))
+                }
+                sp!('/', '/', '!') => {
+                    let (token, remainder) = self.remainder.split_at(line_end(self.remainder));
+                    Ok((
+                        Token::new_auto_span(
+                            TokenType::Comment(Comment::InnerDocComment),
+                            token,
+                            self.line_column,
+                            self.offset(),
                         ),
                         remainder,
                     ))
                 }
                 sp!('/', '/') => {
-                    let (token, remainder) = self
-                        .remainder
-                        .split_at(self.remainder.find('\n').unwrap_or(self.remainder.len()));
+                    let (token, remainder) = self.remainder.split_at(line_end(self.remainder));
                     Ok((
                         Token::new_auto_span(
                             TokenType::Comment(Comment::Comment),
                             token,
                             self.line_column,
+                            self.offset(),
+                        ),
+                        remainder,
+                    ))
+                }
+                sp!('/', '*') => {
+                    let bytes = self.remainder.as_bytes();
+                    let (mut depth, mut i) = (1_usize, 2);
+                    while depth > 0 {
+                        match bytes.get(i..i + 2) {
+                            Some(b"/*") => (depth, i) = (depth + 1, i + 2),
+                            Some(b"*/") => (depth, i) = (depth - 1, i + 2),
+                            Some(_) => i += 1,
+                            None => {
+                                // Everything after the opener is part of the comment
+                                return Some(self.recover(
+                                    ParseTokenError::UnterminatedBlockComment(self.sub_span(0, 2)),
+                                    self.remainder.len(),
+                                ));
+                            }
+                        }
+                    }
+                    // `/**/` and `/***` are not doc comments
+                    let comment = match bytes.get(2..4) {
+                        Some([b'*', b'*' | b'/']) => Comment::BlockComment,
+                        Some([b'*', _]) => Comment::BlockDocComment,
+                        _ => Comment::BlockComment,
+                    };
+                    let (token, remainder) = self.remainder.split_at(i);
+                    Ok((
+                        Token::new_auto_span(
+                            TokenType::Comment(comment),
+                            token,
+                            self.line_column,
+                            self.offset(),
                         ),
                         remainder,
                     ))
                 }
                 // Puncts
-                sp!('不') => st!('不', TokenType::Punct(Punct::Not), self),
-                sp!('≥') => st!('≥', TokenType::Punct(Punct::Ge), self),
-                sp!('≤') => st!('≤', TokenType::Punct(Punct::Le), self),
-                sp!('≔') => st!('≔', TokenType::Punct(Punct::Assign), self),
-                sp!('∨') => st!('∨', TokenType::Punct(Punct::Or), self),
-                sp!('∧') => st!('∧', TokenType::Punct(Punct::And), self),
+                sp!('不') if self.config.unicode_aliases => {
+                    st!('不', TokenType::Punct(Punct::Not), self)
+                }
+                sp!('≥') if self.config.unicode_aliases => {
+                    st!('≥', TokenType::Punct(Punct::Ge), self)
+                }
+                sp!('≤') if self.config.unicode_aliases => {
+                    st!('≤', TokenType::Punct(Punct::Le), self)
+                }
+                sp!('≔') if self.config.unicode_aliases => {
+                    st!('≔', TokenType::Punct(Punct::Assign), self)
+                }
+                sp!('∨') if self.config.unicode_aliases => {
+                    st!('∨', TokenType::Punct(Punct::Or), self)
+                }
+                sp!('∧') if self.config.unicode_aliases => {
+                    st!('∧', TokenType::Punct(Punct::And), self)
+                }
                 sp!('∃') => st!('∃', TokenType::Punct(Punct::Exists), self),
                 sp!('∀') => st!('∀', TokenType::Punct(Punct::ForAll), self),
-                sp!('¬') => st!('¬', TokenType::Punct(Punct::Not), self),
+                sp!('¬') if self.config.unicode_aliases => {
+                    st!('¬', TokenType::Punct(Punct::Not), self)
+                }
                 sp!('~') => st!('~', TokenType::Punct(Punct::Tilde), self),
                 sp!('|') => st!('|', TokenType::Punct(Punct::Or), self),
                 sp!('^') => st!('^', TokenType::Punct(Punct::Caret), self),
@@ -350,90 +774,222 @@ impl<'a> Iterator for SplitTokens<'a> {
                 sp!(')') => st!(')', TokenType::Delimiter(Delimiter::ParRight), self),
                 // String Literals
                 sp!('"') => {
-                    let Some(index) = find_unescaped!('"', self.remainder) else {
-                        return Some(Err(ParseTokenError::UnterminatedStrLit));
+                    let Some(index) = fragment_end(&self.remainder[1..]).map(|i| i + 1) else {
+                        // Resume lexing on the next line
+                        let line_end = line_end(self.remainder);
+                        return Some(self.recover(
+                            ParseTokenError::UnterminatedStrLit(self.sub_span(0, 1)),
+                            line_end,
+                        ));
                     };
+                    if self.remainder[index..].starts_with('{') {
+                        self.modes.push(Mode::String(self.sub_span(0, 1)));
+                        return Some(Ok(
+                            self.emit(TokenType::Interpolation(Interpolation::StrStart), 1)
+                        ));
+                    }
+                    if let Err(error) = parse_escapes(&self.remainder[1..index]) {
+                        return Some(self.recover(self.escape_error(&error, 1), index + 1));
+                    }
                     Ok((
                         Token::new_auto_span(
                             TokenType::Literal(Literal::String),
                             &self.remainder[..=index],
                             self.line_column,
+                            self.offset(),
+                        ),
+                        &self.remainder[index + 1..],
+                    ))
+                }
+                // Byte String Literals
+                sp!('b', '"') => {
+                    let Some(index) = find_unescaped!('"', self.remainder[1..]).map(|i| i + 1)
+                    else {
+                        // Resume lexing on the next line
+                        let line_end = line_end(self.remainder);
+                        return Some(self.recover(
+                            ParseTokenError::UnterminatedStrLit(self.sub_span(0, 2)),
+                            line_end,
+                        ));
+                    };
+                    if let Err(error) = parse_byte_escapes(&self.remainder[2..index]) {
+                        return Some(self.recover(self.escape_error(&error, 2), index + 1));
+                    }
+                    Ok((
+                        Token::new_auto_span(
+                            TokenType::Literal(Literal::ByteString),
+                            &self.remainder[..=index],
+                            self.line_column,
+                            self.offset(),
                         ),
                         &self.remainder[index + 1..],
                     ))
                 }
+                // Byte Literals
+                sp!('b', '\'') => {
+                    let Some(index) = find_unescaped!('\'', self.remainder[1..]).map(|i| i + 1)
+                    else {
+                        // Resume lexing on the next line
+                        let line_end = line_end(self.remainder);
+                        return Some(self.recover(
+                            ParseTokenError::UnterminatedChrLit(self.sub_span(0, 2)),
+                            line_end,
+                        ));
+                    };
+                    if let Err(error) = parse_byte(&self.remainder[2..index]) {
+                        return Some(self.recover(self.char_error(error, index + 1, 2), index + 1));
+                    }
+                    Ok((
+                        Token::new_auto_span(
+                            TokenType::Literal(Literal::Byte),
+                            &self.remainder[..=index],
+                            self.line_column,
+                            self.offset(),
+                        ),
+                        &self.remainder[index + 1..],
+                    ))
+                }
+                // Raw String Literals
+                sp!('r', '"' | '#') if raw_string_hashes(self.remainder).is_some() => {
+                    let hashes = raw_string_hashes(self.remainder).unwrap_or_default();
+                    let content_start = hashes + 2;
+                    let Some(index) = self.remainder[content_start..]
+                        .match_indices('"')
+                        .map(|(i, _)| content_start + i + 1)
+                        .find(|&i| {
+                            self.remainder[i..]
+                                .bytes()
+                                .take_while(|&b| b == b'#')
+                                .count()
+                                >= hashes
+                        })
+                    else {
+                        // Resume lexing on the next line
+                        let line_end = line_end(self.remainder);
+                        return Some(self.recover(
+                            ParseTokenError::UnterminatedStrLit(self.sub_span(0, content_start)),
+                            line_end,
+                        ));
+                    };
+                    let (token, remainder) = self.remainder.split_at(index + hashes);
+                    Ok((
+                        Token::new_auto_span(
+                            TokenType::Literal(Literal::RawString),
+                            token,
+                            self.line_column,
+                            self.offset(),
+                        ),
+                        remainder,
+                    ))
+                }
                 // Char Literals
                 sp!('\'') => {
                     let Some(index) = find_unescaped!('\'', self.remainder) else {
-                        return Some(Err(ParseTokenError::UnterminatedChrLit));
+                        // Resume lexing on the next line
+                        let line_end = line_end(self.remainder);
+                        return Some(self.recover(
+                            ParseTokenError::UnterminatedChrLit(self.sub_span(0, 1)),
+                            line_end,
+                        ));
                     };
+                    if let Err(error) = parse_char(&self.remainder[1..index]) {
+                        return Some(self.recover(self.char_error(error, index + 1, 1), index + 1));
+                    }
                     Ok((
                         Token::new_auto_span(
                             TokenType::Literal(Literal::Character),
                             &self.remainder[..=index],
                             self.line_column,
+                            self.offset(),
                         ),
                         &self.remainder[index + 1..],
                     ))
                 }
                 // Proper Ident
-                (c, _) if c.is_alphabetic() & c.is_uppercase() => {
-                    let (token, remainder) = self.remainder.split_at(
-                        self.remainder
-                            .find(|c: char| !(c.is_alphanumeric() | (c == '_')))
-                            .unwrap_or(self.remainder.len()),
-                    );
-                    if let Some(i) = token.find('_') {
-                        return Some(Err(ParseTokenError::UnderscoreInProper(token, i)));
+                (c, _)
+                    if is_xid_start(c)
+                        && (is_caps(c)
+                            || (is_uncased(c)
+                                && self.config.uncased_idents == UncasedIdents::Proper)) =>
+                {
+                    let (token, remainder) = self.remainder.split_at(ident_end(self.remainder));
+                    if let Some(i) = token.find('_').filter(|_| self.config.case_rules) {
+                        return Some(self.recover(
+                            ParseTokenError::UnderscoreInProper(token, i, self.sub_span(i, i + 1)),
+                            token.len(),
+                        ));
                     }
                     Ok((
-                        Token::new_auto_span(TokenType::ProperIdent, token, self.line_column),
+                        Token::new_auto_span(
+                            TokenType::ProperIdent,
+                            token,
+                            self.line_column,
+                            self.offset(),
+                        ),
                         remainder,
                     ))
                 }
                 // Ident
-                (c, _) if c.is_alphabetic() | (c == '_') => {
-                    let (token, remainder) = self.remainder.split_at(
-                        self.remainder
-                            .find(|c: char| !(c.is_alphanumeric() | (c == '_')))
-                            .unwrap_or(self.remainder.len()),
-                    );
-                    if let Some(i) = token.find(char::is_uppercase) {
-                        return Some(Err(ParseTokenError::CapsInImproperIdent(token, i)));
+                (c, _) if is_xid_start(c) || (c == '_') => {
+                    let (token, remainder) = self.remainder.split_at(ident_end(self.remainder));
+                    if let Some((i, c)) = token
+                        .char_indices()
+                        .find(|&(_, c)| is_caps(c))
+                        .filter(|_| self.config.case_rules)
+                    {
+                        return Some(self.recover(
+                            ParseTokenError::CapsInImproperIdent(
+                                token,
+                                i,
+                                self.sub_span(i, i + c.len_utf8()),
+                            ),
+                            token.len(),
+                        ));
                     }
                     Ok((
                         Token::new_auto_span(
-                            match token {
-                                "if" => TokenType::Keyword(Keyword::If),
-                                "else" => TokenType::Keyword(Keyword::Else),
-                                "match" => TokenType::Keyword(Keyword::Match),
-                                "loop" => TokenType::Keyword(Keyword::Loop),
-                                "let" => TokenType::Keyword(Keyword::Let),
-                                "type" => TokenType::Keyword(Keyword::Type),
-                                "class" => TokenType::Keyword(Keyword::Class),
-                                "ret" => TokenType::Keyword(Keyword::Ret),
-                                "where" => TokenType::Keyword(Keyword::Where),
-                                "miguel" => TokenType::Keyword(Keyword::Miguel),
-                                "kyasig" => TokenType::Keyword(Keyword::Kyasig),
-                                "claim" => TokenType::Keyword(Keyword::Claim),
-                                "cardinality" => TokenType::Keyword(Keyword::Cardinality),
-                                "bytes" => TokenType::Keyword(Keyword::Bytes),
-                                "bits" => TokenType::Keyword(Keyword::Bits),
-                                "fn" => TokenType::Keyword(Keyword::Fn),
+                            match &*nfc(token) {
                                 // Underscore Punct
                                 "_" => TokenType::Punct(Punct::Underscore),
-                                _ => TokenType::Ident,
+                                name => match Keyword::from_name(name) {
+                                    Some(keyword) if keyword.is_contextual() => {
+                                        TokenType::ContextualKeyword(keyword)
+                                    }
+                                    Some(keyword) => TokenType::Keyword(keyword),
+                                    None => TokenType::Ident,
+                                },
                             },
                             token,
                             self.line_column,
+                            self.offset(),
                         ),
                         remainder,
                     ))
                 }
-                (c, _) => Err(ParseTokenError::InvalidChar(
-                    c,
-                    &self.remainder[..c.len_utf8()],
-                )),
+                // Non-ASCII chars that interrupt or would start an identifier
+                (c, _)
+                    if !c.is_ascii()
+                        && (is_xid_continue(c)
+                            || self.original[..self.offset()]
+                                .chars()
+                                .next_back()
+                                .is_some_and(is_xid_continue)) =>
+                {
+                    return Some(self.recover(
+                        ParseTokenError::InvalidIdentChar(c, self.sub_span(0, c.len_utf8())),
+                        c.len_utf8(),
+                    ))
+                }
+                (c, _) => {
+                    return Some(self.recover(
+                        ParseTokenError::InvalidChar(
+                            c,
+                            &self.remainder[..c.len_utf8()],
+                            self.sub_span(0, c.len_utf8()),
+                        ),
+                        c.len_utf8(),
+                    ))
+                }
             }
             .map(|(token, remainder)| {
                 (self.line_column, self.remainder) = (token.span.end, remainder);
@@ -441,15 +997,367 @@ impl<'a> Iterator for SplitTokens<'a> {
             }),
         )
     }
+}
+
+impl<'a> Iterator for SplitTokens<'a> {
+    type Item = Result<Token<'a>, ParseTokenError<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(skipped) = self.skipped.take() {
+            return Some(Ok(skipped));
+        }
+        loop {
+            match self.next_token()? {
+                Ok(Token {
+                    token_type: TokenType::Comment(_),
+                    ..
+                }) if !self.config.comments => {}
+                item => return Some(item),
+            }
+        }
+    }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(self.remainder.len()))
+        // A byte yields at most two items: an error and the text it skipped in lossless mode, or
+        // the opening quote of an interpolated string and its error once the input runs out. The
+        // strings already open can still each report that error too.
+        let open_strings = self
+            .modes
+            .iter()
+            .filter(|mode| matches!(mode, Mode::String(_)))
+            .count();
+        (
+            0,
+            Some(2 * self.remainder.len() + open_strings + usize::from(self.skipped.is_some())),
+        )
     }
 }
 
-impl<'a> FusedIterator for SplitTokens<'a> {}
+impl FusedIterator for SplitTokens<'_> {}
 
 #[must_use]
-pub fn split_tokens(string: &str) -> SplitTokens {
+pub fn split_tokens(string: &str) -> SplitTokens<'_> {
     SplitTokens::new(string, string, LineColumn::new(0, 0))
 }
+
+/// Like [`split_tokens`], but also yields whitespace, newlines and the text skipped after each
+/// error as [`TokenType::Trivia`] tokens, so that concatenating the lexemes reproduces the input
+/// exactly, even when it has errors.
+#[must_use]
+pub fn split_tokens_lossless(string: &str) -> SplitTokens<'_> {
+    SplitTokens::with_config(string, LexerConfig::new().trivia(true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn types_and_lexemes(input: &str) -> Vec<(TokenType, &str)> {
+        split_tokens(input)
+            .map(|result| result.map(|token| (token.token_type, token.lexeme)))
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    fn error_messages(input: &str) -> Vec<String> {
+        split_tokens(input)
+            .filter_map(Result::err)
+            .map(|error| error.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn unicode_escape_braces_are_not_interpolation() {
+        assert_eq!(
+            types_and_lexemes(r#""\u{1F600}""#),
+            [(TokenType::Literal(Literal::String), r#""\u{1F600}""#)]
+        );
+        assert_eq!(
+            types_and_lexemes(r#""a\u{41}{x}""#),
+            [
+                (TokenType::Interpolation(Interpolation::StrStart), "\""),
+                (
+                    TokenType::Interpolation(Interpolation::Fragment),
+                    r"a\u{41}"
+                ),
+                (TokenType::Interpolation(Interpolation::ExprStart), "{"),
+                (TokenType::Ident, "x"),
+                (TokenType::Interpolation(Interpolation::ExprEnd), "}"),
+                (TokenType::Interpolation(Interpolation::StrEnd), "\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn contextual_keywords_can_name_fields() {
+        assert_eq!(
+            types_and_lexemes("x.type"),
+            [
+                (TokenType::Ident, "x"),
+                (TokenType::Punct(Punct::Dot), "."),
+                (TokenType::ContextualKeyword(Keyword::Type), "type"),
+            ]
+        );
+        assert!(TokenType::ContextualKeyword(Keyword::Type).is_ident());
+    }
+
+    #[test]
+    fn mixed_line_endings() {
+        let input = "a\nb\r\nc\rd \"x\r\ny\ry\" e";
+        let tokens = split_tokens_lossless(input)
+            .map(|token| {
+                let token = token.unwrap();
+                (token.lexeme, token.span.start, token.span.end)
+            })
+            .collect::<Vec<_>>();
+        let lc = LineColumn::new;
+        assert_eq!(
+            tokens,
+            [
+                ("a", lc(0, 0), lc(0, 1)),
+                ("\n", lc(0, 1), lc(1, 0)),
+                ("b", lc(1, 0), lc(1, 1)),
+                ("\r\n", lc(1, 1), lc(2, 0)),
+                ("c", lc(2, 0), lc(2, 1)),
+                ("\r", lc(2, 1), lc(3, 0)),
+                ("d", lc(3, 0), lc(3, 1)),
+                (" ", lc(3, 1), lc(3, 2)),
+                ("\"x\r\ny\ry\"", lc(3, 2), lc(5, 2)),
+                (" ", lc(5, 2), lc(5, 3)),
+                ("e", lc(5, 3), lc(5, 4)),
+            ]
+        );
+        assert_eq!(LineColumn::default().advance(input), lc(5, 4));
+    }
+
+    #[test]
+    fn lossless_round_trips_errors() {
+        for input in [
+            "\"unterminated\n x",
+            "a $ b 5zz '' 'ab' /* x",
+            "\"a{b\n c",
+            "\"x\\q\" y",
+        ] {
+            let (tokens, errors) = split_tokens_lossless(input).tokens_and_errors();
+            assert!(!errors.is_empty());
+            assert_eq!(tokens.iter().map(|t| t.lexeme).collect::<String>(), input);
+        }
+    }
+
+    /// Checks the upper bound of `size_hint` before every item of `lexer`.
+    fn check_size_hints(mut lexer: SplitTokens<'_>) {
+        loop {
+            let (_, upper) = lexer.size_hint();
+            let remaining = lexer.clone().count();
+            assert!(remaining <= upper.unwrap(), "{remaining} > {upper:?}");
+            if lexer.next().is_none() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn size_hint_counts_skipped_text() {
+        assert_eq!(split_tokens_lossless("$").count(), 2);
+        for input in ["$", "a $$ 'b\n", "\"x{$}\n"] {
+            check_size_hints(split_tokens(input));
+            check_size_hints(split_tokens_lossless(input));
+        }
+    }
+
+    #[test]
+    fn unterminated_nested_strings_are_all_reported() {
+        let input = "\"{\"{\"{x}";
+        let (_, errors) = split_tokens(input).tokens_and_errors();
+        let openers = errors
+            .iter()
+            .map(|error| match error {
+                ParseTokenError::UnterminatedStrLit(span) => span.bytes.start,
+                error => panic!("unexpected error {error:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(openers, [4, 2, 0]);
+        check_size_hints(split_tokens(input));
+    }
+
+    #[test]
+    fn every_keyword_lexes_by_its_display_name() {
+        for &keyword in Keyword::ALL {
+            let expected = if keyword.is_contextual() {
+                TokenType::ContextualKeyword(keyword)
+            } else {
+                TokenType::Keyword(keyword)
+            };
+            assert_eq!(
+                types_and_lexemes(&keyword.to_string()),
+                [(expected, &*keyword.to_string())]
+            );
+        }
+    }
+
+    #[test]
+    fn config_can_be_read_back() {
+        let config = split_tokens_lossless("").config();
+        assert!(config.is_trivia() && config.is_comments());
+        let config = LexerConfig::new()
+            .unicode_aliases(false)
+            .case_rules(false)
+            .uncased_idents(UncasedIdents::Proper);
+        assert!(!config.is_unicode_aliases() && !config.is_case_rules() && !config.is_trivia());
+        assert_eq!(config.uncased_idents_policy(), UncasedIdents::Proper);
+    }
+
+    #[test]
+    fn byte_literal_errors_say_byte() {
+        let messages = split_tokens("'' 'ab' b'' b'ab'")
+            .filter_map(Result::err)
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            messages,
+            [
+                "1:1: Empty char literal",
+                "1:4: Char literal 'ab' must contain exactly one char",
+                "1:9: Empty byte literal",
+                "1:13: Byte literal b'ab' must contain exactly one byte",
+            ]
+        );
+    }
+
+    #[test]
+    fn prefixed_ints() {
+        let u8_suffix = Some(Suffix::new(SuffixKind::Unsigned, 8));
+        assert_eq!(
+            types_and_lexemes("0b1010 0o17 0xFF_u8 0x_1"),
+            [
+                (TokenType::Literal(Literal::BinInt(None)), "0b1010"),
+                (TokenType::Literal(Literal::OctInt(None)), "0o17"),
+                (TokenType::Literal(Literal::HexInt(u8_suffix)), "0xFF_u8"),
+                (TokenType::Literal(Literal::HexInt(None)), "0x_1"),
+            ]
+        );
+        assert_eq!(
+            error_messages("0b102 0x 0b_ 0o8"),
+            [
+                "1:5: Invalid digit for a base 2 literal: 2",
+                "1:7: No digits in integer literal \"0x\"",
+                "1:10: No digits in integer literal \"0b_\"",
+                "1:16: Invalid digit for a base 8 literal: 8",
+            ]
+        );
+    }
+
+    #[test]
+    fn exponents_and_suffixes() {
+        let f32_suffix = Some(Suffix::new(SuffixKind::Float, 32));
+        assert_eq!(
+            types_and_lexemes("1e5 2.5e-3 7E+2 1.5f32 5f32 0xABf32"),
+            [
+                (TokenType::Literal(Literal::NonInt(None)), "1e5"),
+                (TokenType::Literal(Literal::NonInt(None)), "2.5e-3"),
+                (TokenType::Literal(Literal::NonInt(None)), "7E+2"),
+                (TokenType::Literal(Literal::NonInt(f32_suffix)), "1.5f32"),
+                (TokenType::Literal(Literal::NonInt(f32_suffix)), "5f32"),
+                // `f32` are hex digits
+                (TokenType::Literal(Literal::HexInt(None)), "0xABf32"),
+            ]
+        );
+        assert_eq!(
+            error_messages("1e5u8 1e 1u7"),
+            [
+                "1:4: Invalid numeric literal suffix \"u8\"",
+                "1:8: Invalid numeric literal suffix \"e\"",
+                "1:11: Invalid numeric literal suffix \"u7\"",
+            ]
+        );
+    }
+
+    #[test]
+    fn block_and_doc_comments() {
+        assert_eq!(
+            types_and_lexemes("/**/ /***/ /** doc */ /* a /* b */ c */ x"),
+            [
+                (TokenType::Comment(Comment::BlockComment), "/**/"),
+                (TokenType::Comment(Comment::BlockComment), "/***/"),
+                (TokenType::Comment(Comment::BlockDocComment), "/** doc */"),
+                (
+                    TokenType::Comment(Comment::BlockComment),
+                    "/* a /* b */ c */"
+                ),
+                (TokenType::Ident, "x"),
+            ]
+        );
+        assert_eq!(
+            types_and_lexemes("//! inner\n/// doc\n// plain\n"),
+            [
+                (TokenType::Comment(Comment::InnerDocComment), "//! inner"),
+                (TokenType::Comment(Comment::DocComment), "/// doc"),
+                (TokenType::Comment(Comment::Comment), "// plain"),
+            ]
+        );
+        assert_eq!(
+            error_messages("x /*** /* */"),
+            ["1:3: No block comment terminator found!"]
+        );
+    }
+
+    #[test]
+    fn unicode_identifiers() {
+        assert_eq!(
+            types_and_lexemes("café naïve a·b 日本 e\u{301}"),
+            [
+                (TokenType::Ident, "café"),
+                (TokenType::Ident, "naïve"),
+                (TokenType::Ident, "a·b"),
+                (TokenType::Ident, "日本"),
+                (TokenType::Ident, "e\u{301}"),
+            ]
+        );
+        let token = split_tokens("cafe\u{301}").next().unwrap().unwrap();
+        assert_eq!(token.normalized(), "caf\u{e9}");
+        assert_eq!(
+            error_messages("a\u{200B}b €x x€"),
+            [
+                "1:2: Char '\\u{200b}' (U+200B) is not allowed in identifiers",
+                "1:5: Invalid char: €",
+                "1:9: Char '€' (U+20AC) is not allowed in identifiers",
+            ]
+        );
+    }
+
+    #[test]
+    fn uncased_and_titlecase_identifiers() {
+        let lex = |policy| {
+            SplitTokens::with_config(
+                "ǅemal 日本 T型 _日 x日",
+                LexerConfig::new().uncased_idents(policy),
+            )
+            .map(|result| result.unwrap().token_type)
+            .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lex(UncasedIdents::Improper),
+            [
+                TokenType::ProperIdent,
+                TokenType::Ident,
+                TokenType::ProperIdent,
+                TokenType::Ident,
+                TokenType::Ident,
+            ]
+        );
+        assert_eq!(
+            lex(UncasedIdents::Proper),
+            [
+                TokenType::ProperIdent,
+                TokenType::ProperIdent,
+                TokenType::ProperIdent,
+                TokenType::Ident,
+                TokenType::Ident,
+            ]
+        );
+        assert_eq!(
+            error_messages("aǅ"),
+            ["1:2: Caps in improper identifier, \"aǅ\", at pos 1"]
+        );
+    }
+}
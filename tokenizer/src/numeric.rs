@@ -0,0 +1,502 @@
+use std::fmt::{Display, Formatter};
+
+use crate::types::{Suffix, SuffixKind};
+
+/// Returns whether `c` may appear among the digits of a base `radix` literal.
+pub(crate) fn is_digit_or_separator(c: char, radix: u32) -> bool {
+    c.is_digit(radix) || (c == '_')
+}
+
+/// Splits an integer lexeme into its sign, its radix and its digits, which may contain `_`.
+pub(crate) fn split_int(lexeme: &str) -> (bool, u32, &str) {
+    let (negative, unsigned) = match lexeme.as_bytes().first() {
+        Some(b'-') => (true, &lexeme[1..]),
+        Some(b'+') => (false, &lexeme[1..]),
+        _ => (false, lexeme),
+    };
+    let (radix, digits) = match unsigned.as_bytes() {
+        [b'0', b'x', ..] => (16, &unsigned[2..]),
+        [b'0', b'o', ..] => (8, &unsigned[2..]),
+        [b'0', b'b', ..] => (2, &unsigned[2..]),
+        _ => (10, unsigned),
+    };
+    (negative, radix, digits)
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ParseNumberError {
+    /// A char that isn't a digit of the literal's base, with its byte offset in the lexeme.
+    InvalidDigit(char, usize),
+    MissingDigits,
+    /// The value doesn't fit in the requested bit width and signedness.
+    Overflow(u32, bool),
+    ExponentOverflow,
+}
+
+impl Display for ParseNumberError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDigit(c, i) => write!(f, "Invalid digit {c:?} at pos {i}"),
+            Self::MissingDigits => write!(f, "No digits in numeric literal"),
+            Self::Overflow(bits, true) => write!(f, "Literal out of range for i{bits}"),
+            Self::Overflow(bits, false) => write!(f, "Literal out of range for u{bits}"),
+            Self::ExponentOverflow => write!(f, "Exponent of numeric literal out of range"),
+        }
+    }
+}
+
+impl std::error::Error for ParseNumberError {}
+
+/// An exact integer of arbitrary width.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct IntValue {
+    negative: bool,
+    /// Little-endian base 2<sup>64</sup> limbs with no trailing zero limbs.
+    magnitude: Vec<u64>,
+}
+
+impl IntValue {
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    #[must_use]
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_empty()
+    }
+
+    /// The absolute value as little-endian 64-bit limbs.
+    #[must_use]
+    pub fn magnitude(&self) -> &[u64] {
+        &self.magnitude
+    }
+
+    /// The number of bits needed to represent the absolute value.
+    #[must_use]
+    pub fn magnitude_bits(&self) -> u64 {
+        self.magnitude.last().map_or(0, |last| {
+            64 * (self.magnitude.len() as u64 - 1) + u64::from(64 - last.leading_zeros())
+        })
+    }
+
+    /// Checks that the value fits in an integer of `bits` bits.
+    /// # Errors
+    /// Returns [`ParseNumberError::Overflow`] if it doesn't.
+    pub fn check_width(&self, bits: u32, signed: bool) -> Result<(), ParseNumberError> {
+        let magnitude_bits = self.magnitude_bits();
+        let fits = match (signed, self.negative) {
+            (false, false) => magnitude_bits <= u64::from(bits),
+            (false, true) => false,
+            (true, false) => magnitude_bits < u64::from(bits),
+            // -2^(bits - 1) is the only value needing all bits
+            (true, true) => {
+                magnitude_bits < u64::from(bits)
+                    || (magnitude_bits == u64::from(bits)
+                        && self
+                            .magnitude
+                            .iter()
+                            .map(|limb| limb.count_ones())
+                            .sum::<u32>()
+                            == 1)
+            }
+        };
+        if fits {
+            Ok(())
+        } else {
+            Err(ParseNumberError::Overflow(bits, signed))
+        }
+    }
+
+    /// # Errors
+    /// Returns [`ParseNumberError::Overflow`] if the value doesn't fit in a `u128`.
+    pub fn to_u128(&self) -> Result<u128, ParseNumberError> {
+        self.check_width(128, false)?;
+        Ok(self.low_u128())
+    }
+
+    /// # Errors
+    /// Returns [`ParseNumberError::Overflow`] if the value doesn't fit in an `i128`.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn to_i128(&self) -> Result<i128, ParseNumberError> {
+        self.check_width(128, true)?;
+        let low = self.low_u128() as i128;
+        Ok(if self.negative {
+            low.wrapping_neg()
+        } else {
+            low
+        })
+    }
+
+    fn low_u128(&self) -> u128 {
+        self.magnitude
+            .iter()
+            .take(2)
+            .rev()
+            .fold(0, |acc, &limb| (acc << 64_u32) | u128::from(limb))
+    }
+
+    /// Sets `self` to `self * factor + addend`, ignoring the sign.
+    #[allow(clippy::cast_possible_truncation)]
+    fn mul_add(&mut self, factor: u64, addend: u64) {
+        let carry = self.magnitude.iter_mut().fold(addend, |carry, limb| {
+            let product = u128::from(*limb) * u128::from(factor) + u128::from(carry);
+            *limb = product as u64;
+            (product >> 64_u32) as u64
+        });
+        if carry != 0 {
+            self.magnitude.push(carry);
+        }
+    }
+
+    /// Sets `self` to `self / divisor`, ignoring the sign, and returns the remainder.
+    #[allow(clippy::cast_possible_truncation)]
+    fn div_rem(&mut self, divisor: u64) -> u64 {
+        let remainder = self.magnitude.iter_mut().rev().fold(0, |remainder, limb| {
+            let dividend = (u128::from(remainder) << 64_u32) | u128::from(*limb);
+            *limb = (dividend / u128::from(divisor)) as u64;
+            (dividend % u128::from(divisor)) as u64
+        });
+        while self.magnitude.last() == Some(&0) {
+            self.magnitude.pop();
+        }
+        remainder
+    }
+}
+
+impl Display for IntValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut rest = self.clone();
+        let mut chunks = vec![rest.div_rem(CHUNK)];
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem(CHUNK));
+        }
+        if self.negative {
+            write!(f, "-")?;
+        }
+        chunks.iter().rev().enumerate().try_for_each(|(i, chunk)| {
+            if i == 0 {
+                write!(f, "{chunk}")
+            } else {
+                write!(f, "{chunk:019}")
+            }
+        })
+    }
+}
+
+/// An exact decimal number, equal to `digits` &times; 10<sup>`exponent`</sup>.
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct DecimalValue {
+    negative: bool,
+    /// ASCII decimal digits without separators or leading zeros.
+    digits: String,
+    exponent: i64,
+}
+
+impl DecimalValue {
+    #[must_use]
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The significant decimal digits, empty for zero.
+    #[must_use]
+    pub fn digits(&self) -> &str {
+        &self.digits
+    }
+
+    #[must_use]
+    pub fn exponent(&self) -> i64 {
+        self.exponent
+    }
+
+    /// The signed significand, so that the value is `mantissa` &times; 10<sup>`exponent`</sup>.
+    #[must_use]
+    pub fn mantissa(&self) -> IntValue {
+        let mut mantissa = IntValue::default();
+        self.digits
+            .bytes()
+            .for_each(|digit| mantissa.mul_add(10, u64::from(digit - b'0')));
+        mantissa.negative = self.negative && !mantissa.is_zero();
+        mantissa
+    }
+
+    /// The nearest `f64`, rounding ties to even.
+    #[must_use]
+    pub fn to_f64(&self) -> f64 {
+        let sign = if self.negative { "-" } else { "" };
+        let digits = if self.digits.is_empty() {
+            "0"
+        } else {
+            &self.digits
+        };
+        format!("{sign}{digits}e{}", self.exponent)
+            .parse()
+            .unwrap_or(f64::NAN)
+    }
+}
+
+/// Parses the lexeme of a decimal, hexadecimal, octal or binary integer literal into its exact value.
+/// A type suffix, if any, is ignored; see [`Literal::suffix`](crate::Literal::suffix).
+/// # Errors
+/// Returns `Err` if the lexeme has no digits or has a digit outside its base.
+pub fn parse_int(lexeme: &str) -> Result<IntValue, ParseNumberError> {
+    let (negative, radix, digits) = split_int(lexeme);
+    let digits_start = lexeme.len() - digits.len();
+    let digits_end = digits
+        .find(|c: char| !is_digit_or_separator(c, radix))
+        .unwrap_or(digits.len());
+    check_suffix(lexeme, digits_start + digits_end)?;
+    let mut value = IntValue::default();
+    let mut any_digits = false;
+    digits[..digits_end]
+        .chars()
+        .filter_map(|c| c.to_digit(radix))
+        .for_each(|digit| {
+            value.mul_add(u64::from(radix), u64::from(digit));
+            any_digits = true;
+        });
+    if !any_digits {
+        return Err(ParseNumberError::MissingDigits);
+    }
+    value.negative = negative && !value.is_zero();
+    Ok(value)
+}
+
+/// Parses the lexeme of a decimal integer or non-integer literal, including its exponent, into its
+/// exact value. A type suffix, if any, is ignored.
+/// # Errors
+/// Returns `Err` if the lexeme has no digits, has a char that isn't a decimal digit, `_`, a single
+/// `.` or an exponent, or has an exponent too large to represent.
+pub fn parse_decimal(lexeme: &str) -> Result<DecimalValue, ParseNumberError> {
+    let (negative, unsigned) = match lexeme.as_bytes().first() {
+        Some(b'-') => (true, &lexeme[1..]),
+        Some(b'+') => (false, &lexeme[1..]),
+        _ => (false, lexeme),
+    };
+    let sign_len = lexeme.len() - unsigned.len();
+    let mut value = DecimalValue {
+        negative,
+        ..DecimalValue::default()
+    };
+    let mantissa_end = unsigned
+        .find(|c: char| !(is_digit_or_separator(c, 10) || (c == '.')))
+        .unwrap_or(unsigned.len());
+    let (mut any_digits, mut fraction) = (false, false);
+    unsigned[..mantissa_end]
+        .char_indices()
+        .try_for_each(|(i, c)| {
+            match c {
+                '0'..='9' => {
+                    any_digits = true;
+                    if (c != '0') || !value.digits.is_empty() {
+                        value.digits.push(c);
+                    }
+                    value.exponent -= i64::from(fraction);
+                }
+                '_' => {}
+                '.' if !fraction => fraction = true,
+                _ => return Err(ParseNumberError::InvalidDigit(c, sign_len + i)),
+            }
+            Ok(())
+        })?;
+    if !any_digits {
+        return Err(ParseNumberError::MissingDigits);
+    }
+    let mut rest_start = sign_len + mantissa_end;
+    if let Some(exponent) = lexeme[rest_start..].strip_prefix(['e', 'E']) {
+        let (exponent_negative, exponent_digits) = match exponent.as_bytes().first() {
+            Some(b'-') => (true, &exponent[1..]),
+            Some(b'+') => (false, &exponent[1..]),
+            _ => (false, exponent),
+        };
+        let exponent_end = exponent_digits
+            .find(|c: char| !is_digit_or_separator(c, 10))
+            .unwrap_or(exponent_digits.len());
+        if exponent_digits[..exponent_end].contains(|c: char| c.is_ascii_digit()) {
+            // Zero stays zero whatever the exponent, so it can't overflow
+            if !value.digits.is_empty() {
+                let exponent = exponent_digits[..exponent_end]
+                    .chars()
+                    .filter_map(|c| c.to_digit(10))
+                    .try_fold(0_i64, |acc, digit| {
+                        acc.checked_mul(10)?.checked_add(i64::from(digit))
+                    })
+                    .ok_or(ParseNumberError::ExponentOverflow)?;
+                value.exponent = if exponent_negative {
+                    value.exponent.checked_sub(exponent)
+                } else {
+                    value.exponent.checked_add(exponent)
+                }
+                .ok_or(ParseNumberError::ExponentOverflow)?;
+            }
+            rest_start = lexeme.len() - exponent_digits.len() + exponent_end;
+        }
+    }
+    check_suffix(lexeme, rest_start)?;
+    let trailing_zeros = value.digits.len() - value.digits.trim_end_matches('0').len();
+    value.digits.truncate(value.digits.len() - trailing_zeros);
+    value.exponent = i64::try_from(trailing_zeros)
+        .ok()
+        .and_then(|trailing_zeros| value.exponent.checked_add(trailing_zeros))
+        .ok_or(ParseNumberError::ExponentOverflow)?;
+    if value.digits.is_empty() {
+        value.exponent = 0;
+    }
+    Ok(value)
+}
+
+/// Parses the type suffix of a numeric literal, such as `u8` or `f64`.
+pub(crate) fn parse_suffix(s: &str) -> Option<Suffix> {
+    let (kind, bits) = match s {
+        "i8" => (SuffixKind::Signed, 8),
+        "i16" => (SuffixKind::Signed, 16),
+        "i32" => (SuffixKind::Signed, 32),
+        "i64" => (SuffixKind::Signed, 64),
+        "i128" => (SuffixKind::Signed, 128),
+        "u8" => (SuffixKind::Unsigned, 8),
+        "u16" => (SuffixKind::Unsigned, 16),
+        "u32" => (SuffixKind::Unsigned, 32),
+        "u64" => (SuffixKind::Unsigned, 64),
+        "u128" => (SuffixKind::Unsigned, 128),
+        "f32" => (SuffixKind::Float, 32),
+        "f64" => (SuffixKind::Float, 64),
+        _ => return None,
+    };
+    Some(Suffix::new(kind, bits))
+}
+
+/// Checks that everything from byte `start` of `lexeme` on is either empty or a type suffix.
+fn check_suffix(lexeme: &str, start: usize) -> Result<(), ParseNumberError> {
+    match lexeme[start..].chars().next() {
+        Some(c) if parse_suffix(&lexeme[start..]).is_none() => {
+            Err(ParseNumberError::InvalidDigit(c, start))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TWO_TO_128: &str = "340282366920938463463374607431768211456";
+
+    #[test]
+    fn int_widths() {
+        assert_eq!(parse_int("-128").unwrap().check_width(8, true), Ok(()));
+        assert_eq!(
+            parse_int("-129").unwrap().check_width(8, true),
+            Err(ParseNumberError::Overflow(8, true))
+        );
+        assert!(parse_int("128").unwrap().check_width(8, true).is_err());
+        assert!(parse_int("-0").unwrap().check_width(8, false).is_ok());
+        assert!(parse_int("-1").unwrap().check_width(8, false).is_err());
+        assert_eq!(parse_int("0xFF").unwrap().check_width(8, false), Ok(()));
+        assert!(parse_int("0b1_0000_0000")
+            .unwrap()
+            .check_width(8, false)
+            .is_err());
+        assert_eq!(
+            parse_int("-0x8000_0000_0000_0000_0000_0000_0000_0000")
+                .unwrap()
+                .to_i128(),
+            Ok(i128::MIN)
+        );
+        assert!(parse_int("-0x8000_0000_0000_0000_0000_0000_0000_0001")
+            .unwrap()
+            .to_i128()
+            .is_err());
+    }
+
+    #[test]
+    fn int_limbs_and_display() {
+        let max = parse_int(&u128::MAX.to_string()).unwrap();
+        assert_eq!(max.to_u128(), Ok(u128::MAX));
+        assert_eq!(max.magnitude(), [u64::MAX, u64::MAX]);
+
+        let big = parse_int(TWO_TO_128).unwrap();
+        assert_eq!(big.magnitude(), [0, 0, 1]);
+        assert_eq!(big.magnitude_bits(), 129);
+        assert_eq!(big.to_u128(), Err(ParseNumberError::Overflow(128, false)));
+        assert_eq!(big.to_string(), TWO_TO_128);
+        assert_eq!(
+            parse_int(&format!("-{TWO_TO_128}")).unwrap().to_string(),
+            format!("-{TWO_TO_128}")
+        );
+
+        for value in [
+            "0",
+            "10000000000000000000",
+            "10000000000000000005",
+            "-99999999999999999999",
+        ] {
+            assert_eq!(parse_int(value).unwrap().to_string(), value);
+        }
+        assert_eq!(parse_int("0o777u16").unwrap().to_u128(), Ok(0o777));
+    }
+
+    #[test]
+    fn int_errors() {
+        assert_eq!(parse_int("0x"), Err(ParseNumberError::MissingDigits));
+        assert_eq!(parse_int("0b_"), Err(ParseNumberError::MissingDigits));
+        assert_eq!(
+            parse_int("0b102"),
+            Err(ParseNumberError::InvalidDigit('2', 4))
+        );
+        assert_eq!(
+            parse_int("12zz"),
+            Err(ParseNumberError::InvalidDigit('z', 2))
+        );
+    }
+
+    #[test]
+    fn decimals() {
+        let check = |lexeme: &str, digits: &str, exponent: i64| {
+            let value = parse_decimal(lexeme).unwrap();
+            assert_eq!(
+                (value.digits(), value.exponent()),
+                (digits, exponent),
+                "{lexeme}"
+            );
+        };
+        check("0.000", "", 0);
+        check("1.5e-10", "15", -11);
+        check("1_200", "12", 2);
+        check("0012.3400E+2", "1234", 0);
+        check("1.5f32", "15", -1);
+        check("0e99999999999999999999", "", 0);
+        check("0.0e-99999999999999999999", "", 0);
+
+        let value = parse_decimal("-1.5e-10").unwrap();
+        assert!(value.is_negative());
+        assert_eq!(value.mantissa().to_i128(), Ok(-15));
+        assert!((value.to_f64() + 1.5e-10).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn decimal_errors() {
+        assert_eq!(
+            parse_decimal("1e"),
+            Err(ParseNumberError::InvalidDigit('e', 1))
+        );
+        assert_eq!(parse_decimal("."), Err(ParseNumberError::MissingDigits));
+        assert_eq!(
+            parse_decimal("1.2.3"),
+            Err(ParseNumberError::InvalidDigit('.', 3))
+        );
+        assert_eq!(
+            parse_decimal("1e99999999999999999999"),
+            Err(ParseNumberError::ExponentOverflow)
+        );
+        assert_eq!(
+            parse_decimal(&format!("10e{}", i64::MAX)),
+            Err(ParseNumberError::ExponentOverflow)
+        );
+        assert_eq!(
+            parse_decimal(&format!("1e{}", i64::MAX)).map(|value| value.exponent()),
+            Ok(i64::MAX)
+        );
+    }
+}
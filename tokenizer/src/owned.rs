@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+use crate::{EscapeErrorKind, ParseTokenError, Span, Token, TokenType};
+
+/// A [`Token`] that owns its lexeme, so it can outlive the source and be sent across threads.
+/// Cloning it only bumps a reference count.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct OwnedToken {
+    pub token_type: TokenType,
+    pub lexeme: Arc<str>,
+    pub span: Span,
+}
+
+impl OwnedToken {
+    #[must_use]
+    pub fn new(token_type: TokenType, lexeme: Arc<str>, span: Span) -> Self {
+        Self {
+            token_type,
+            lexeme,
+            span,
+        }
+    }
+
+    /// Converts `tokens`, allocating each distinct lexeme once and sharing it between the tokens
+    /// that spell it.
+    pub fn from_tokens<'a, I: IntoIterator<Item = Token<'a>>>(tokens: I) -> Vec<Self> {
+        let mut lexemes = HashMap::<&str, Arc<str>>::new();
+        tokens
+            .into_iter()
+            .map(|token| {
+                let lexeme = lexemes
+                    .entry(token.lexeme)
+                    .or_insert_with(|| token.lexeme.into());
+                Self::new(token.token_type, Arc::clone(lexeme), token.span)
+            })
+            .collect()
+    }
+
+    /// Borrows this token as a [`Token`].
+    #[must_use]
+    pub fn as_token(&self) -> Token<'_> {
+        Token::new(self.token_type, &self.lexeme, self.span)
+    }
+}
+
+impl Token<'_> {
+    #[must_use]
+    pub fn into_owned(self) -> OwnedToken {
+        OwnedToken::new(self.token_type, self.lexeme.into(), self.span)
+    }
+}
+
+impl From<Token<'_>> for OwnedToken {
+    fn from(token: Token<'_>) -> Self {
+        token.into_owned()
+    }
+}
+
+impl Display for OwnedToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.as_token().fmt(f)
+    }
+}
+
+/// A [`ParseTokenError`] that owns the text it refers to, so it can be boxed into a
+/// `Box<dyn Error + Send + Sync>`.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum OwnedParseTokenError {
+    InvalidChar(char, Arc<str>, Span),
+    InvalidIdentChar(char, Span),
+    CapsInImproperIdent(Arc<str>, usize, Span),
+    UnderscoreInProper(Arc<str>, usize, Span),
+    UnterminatedStrLit(Span),
+    UnterminatedChrLit(Span),
+    InvalidEscape(EscapeErrorKind, Span),
+    InvalidDigit(char, u32, Span),
+    MissingDigits(Arc<str>, Span),
+    InvalidSuffix(Arc<str>, Span),
+    UnterminatedBlockComment(Span),
+    EmptyChrLit(Span),
+    MultiCharChrLit(Arc<str>, Span),
+    EmptyByteLit(Span),
+    MultiCharByteLit(Arc<str>, Span),
+}
+
+impl OwnedParseTokenError {
+    /// The span of the offending characters.
+    #[must_use]
+    pub fn span(&self) -> Span {
+        self.as_error().span()
+    }
+
+    /// Borrows this error as a [`ParseTokenError`].
+    #[must_use]
+    pub fn as_error(&self) -> ParseTokenError<'_> {
+        match self {
+            Self::InvalidChar(c, s, span) => ParseTokenError::InvalidChar(*c, s, *span),
+            Self::InvalidIdentChar(c, span) => ParseTokenError::InvalidIdentChar(*c, *span),
+            Self::CapsInImproperIdent(s, i, span) => {
+                ParseTokenError::CapsInImproperIdent(s, *i, *span)
+            }
+            Self::UnderscoreInProper(s, i, span) => {
+                ParseTokenError::UnderscoreInProper(s, *i, *span)
+            }
+            Self::UnterminatedStrLit(span) => ParseTokenError::UnterminatedStrLit(*span),
+            Self::UnterminatedChrLit(span) => ParseTokenError::UnterminatedChrLit(*span),
+            Self::InvalidEscape(kind, span) => ParseTokenError::InvalidEscape(*kind, *span),
+            Self::InvalidDigit(c, radix, span) => ParseTokenError::InvalidDigit(*c, *radix, *span),
+            Self::MissingDigits(s, span) => ParseTokenError::MissingDigits(s, *span),
+            Self::InvalidSuffix(s, span) => ParseTokenError::InvalidSuffix(s, *span),
+            Self::UnterminatedBlockComment(span) => {
+                ParseTokenError::UnterminatedBlockComment(*span)
+            }
+            Self::EmptyChrLit(span) => ParseTokenError::EmptyChrLit(*span),
+            Self::MultiCharChrLit(s, span) => ParseTokenError::MultiCharChrLit(s, *span),
+            Self::EmptyByteLit(span) => ParseTokenError::EmptyByteLit(*span),
+            Self::MultiCharByteLit(s, span) => ParseTokenError::MultiCharByteLit(s, *span),
+        }
+    }
+}
+
+impl ParseTokenError<'_> {
+    #[must_use]
+    pub fn into_owned(self) -> OwnedParseTokenError {
+        match self {
+            Self::InvalidChar(c, s, span) => OwnedParseTokenError::InvalidChar(c, s.into(), span),
+            Self::InvalidIdentChar(c, span) => OwnedParseTokenError::InvalidIdentChar(c, span),
+            Self::CapsInImproperIdent(s, i, span) => {
+                OwnedParseTokenError::CapsInImproperIdent(s.into(), i, span)
+            }
+            Self::UnderscoreInProper(s, i, span) => {
+                OwnedParseTokenError::UnderscoreInProper(s.into(), i, span)
+            }
+            Self::UnterminatedStrLit(span) => OwnedParseTokenError::UnterminatedStrLit(span),
+            Self::UnterminatedChrLit(span) => OwnedParseTokenError::UnterminatedChrLit(span),
+            Self::InvalidEscape(kind, span) => OwnedParseTokenError::InvalidEscape(kind, span),
+            Self::InvalidDigit(c, radix, span) => {
+                OwnedParseTokenError::InvalidDigit(c, radix, span)
+            }
+            Self::MissingDigits(s, span) => OwnedParseTokenError::MissingDigits(s.into(), span),
+            Self::InvalidSuffix(s, span) => OwnedParseTokenError::InvalidSuffix(s.into(), span),
+            Self::UnterminatedBlockComment(span) => {
+                OwnedParseTokenError::UnterminatedBlockComment(span)
+            }
+            Self::EmptyChrLit(span) => OwnedParseTokenError::EmptyChrLit(span),
+            Self::MultiCharChrLit(s, span) => OwnedParseTokenError::MultiCharChrLit(s.into(), span),
+            Self::EmptyByteLit(span) => OwnedParseTokenError::EmptyByteLit(span),
+            Self::MultiCharByteLit(s, span) => {
+                OwnedParseTokenError::MultiCharByteLit(s.into(), span)
+            }
+        }
+    }
+}
+
+impl From<ParseTokenError<'_>> for OwnedParseTokenError {
+    fn from(error: ParseTokenError<'_>) -> Self {
+        error.into_owned()
+    }
+}
+
+impl Display for OwnedParseTokenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.as_error().fmt(f)
+    }
+}
+
+impl Error for OwnedParseTokenError {}
@@ -1,28 +1,87 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
 #[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq)]
 pub struct Span {
     pub start: LineColumn,
     pub end: LineColumn,
+    pub bytes: ByteSpan,
 }
 
 impl Span {
     #[must_use]
-    pub fn new(start: LineColumn, end: LineColumn) -> Self {
-        Self { start, end }
+    pub fn new(start: LineColumn, end: LineColumn, bytes: ByteSpan) -> Self {
+        Self { start, end, bytes }
     }
 }
 
-impl From<(LineColumn, LineColumn)> for Span {
-    fn from((start, end): (LineColumn, LineColumn)) -> Self {
-        Self { start, end }
+impl From<(LineColumn, LineColumn, ByteSpan)> for Span {
+    fn from((start, end, bytes): (LineColumn, LineColumn, ByteSpan)) -> Self {
+        Self { start, end, bytes }
     }
 }
 
-impl From<Span> for (LineColumn, LineColumn) {
+impl From<Span> for (LineColumn, LineColumn, ByteSpan) {
     fn from(span: Span) -> Self {
-        (span.start, span.end)
+        (span.start, span.end, span.bytes)
     }
 }
 
+/// A half-open range of byte offsets into the lexed input.
+#[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq)]
+pub struct ByteSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl ByteSpan {
+    #[must_use]
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the text this span covers in `source`, or `None` if it is out of bounds or not on
+    /// char boundaries.
+    #[must_use]
+    pub fn slice<'a>(&self, source: &'a str) -> Option<&'a str> {
+        source.get(self.range())
+    }
+}
+
+impl From<Range<usize>> for ByteSpan {
+    fn from(range: Range<usize>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl From<ByteSpan> for Range<usize> {
+    fn from(byte_span: ByteSpan) -> Self {
+        byte_span.start..byte_span.end
+    }
+}
+
+/// A zero-based position, with `column` counted in chars. See
+/// [`LineIndex`](crate::LineIndex) for other column units.
 #[derive(Copy, Clone, Default, Debug, Eq, Hash, PartialEq)]
 pub struct LineColumn {
     pub line: usize,
@@ -34,6 +93,25 @@ impl LineColumn {
     pub fn new(line: usize, column: usize) -> Self {
         Self { line, column }
     }
+
+    /// Returns the position reached after walking over `s` starting from `self`. `\n`, `\r\n` and
+    /// a lone `\r` each end a line.
+    #[must_use]
+    pub fn advance(mut self, s: &str) -> Self {
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\r' {
+                chars.next_if_eq(&'\n');
+            }
+            if matches!(c, '\n' | '\r') {
+                self.column = 0;
+                self.line += 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        self
+    }
 }
 
 impl From<(usize, usize)> for LineColumn {
@@ -65,39 +143,58 @@ impl<'a> Token<'a> {
         }
     }
 
+    /// Creates a token starting at `line_column` and byte `offset`, deriving its end from `lexeme`.
     #[must_use]
     pub fn new_auto_span(
         token_type: TokenType,
         lexeme: &'a str,
-        mut line_column: LineColumn,
+        line_column: LineColumn,
+        offset: usize,
     ) -> Self {
         Self {
             token_type,
             lexeme,
-            span: Span::new(line_column, {
-                lexeme.chars().for_each(|c| {
-                    if c == '\n' {
-                        line_column.column = 0;
-                        line_column.line += 1;
-                    } else {
-                        line_column.column += 1;
-                    }
-                });
-                line_column
-            }),
+            span: Span::new(
+                line_column,
+                line_column.advance(lexeme),
+                ByteSpan::new(offset, offset + lexeme.len()),
+            ),
         }
     }
+
+    /// The lexeme in Unicode Normalization Form C, so that identifiers spelled with different but
+    /// canonically equivalent sequences, such as `é` and `e\u{301}`, compare equal.
+    #[must_use]
+    pub fn normalized(&self) -> Cow<'a, str> {
+        nfc(self.lexeme)
+    }
+}
+
+/// Returns `s` in Unicode Normalization Form C, borrowing it if it already is.
+#[must_use]
+pub fn nfc(s: &str) -> Cow<'_, str> {
+    match is_nfc_quick(s.chars()) {
+        IsNormalized::Yes => Cow::Borrowed(s),
+        IsNormalized::No | IsNormalized::Maybe => Cow::Owned(s.nfc().collect()),
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum TokenType {
     Keyword(Keyword),
+    /// An identifier that is also a keyword in some positions, such as `where`. The parser decides
+    /// which one it is from context.
+    ContextualKeyword(Keyword),
     Ident,
     ProperIdent,
     Literal(Literal),
     Punct(Punct),
     Delimiter(Delimiter),
     Comment(Comment),
+    Interpolation(Interpolation),
+    /// Only produced by [`split_tokens_lossless`](crate::split_tokens_lossless), or when enabled
+    /// with [`LexerConfig::trivia`](crate::LexerConfig::trivia).
+    Trivia(Trivia),
 }
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
@@ -124,11 +221,98 @@ pub enum Keyword {
 pub enum Literal {
     Character,
     String,
-    DecInt,
-    HexInt,
-    OctInt,
-    BinInt,
-    NonInt,
+    /// `r"..."`, or `r#"..."#` with any number of `#`s, whose content is taken verbatim.
+    RawString,
+    /// `b'x'`, an ASCII char or `\xNN` escape standing for one byte.
+    Byte,
+    /// `b"..."`, ASCII chars and escapes standing for a byte string.
+    ByteString,
+    DecInt(Option<Suffix>),
+    HexInt(Option<Suffix>),
+    OctInt(Option<Suffix>),
+    BinInt(Option<Suffix>),
+    /// A decimal literal with a fractional part, an exponent or a float suffix, such as `1.5e-10`
+    /// or `5f32`.
+    NonInt(Option<Suffix>),
+}
+
+impl TokenType {
+    /// Whether this token can be used as an identifier, which includes contextual keywords.
+    #[must_use]
+    pub fn is_ident(&self) -> bool {
+        matches!(self, Self::Ident | Self::ContextualKeyword(_))
+    }
+}
+
+impl Keyword {
+    pub const ALL: &'static [Self] = &[
+        Self::If,
+        Self::Else,
+        Self::Match,
+        Self::Loop,
+        Self::Let,
+        Self::Type,
+        Self::Class,
+        Self::Ret,
+        Self::Where,
+        Self::Miguel,
+        Self::Kyasig,
+        Self::Claim,
+        Self::Cardinality,
+        Self::Bytes,
+        Self::Bits,
+        Self::Fn,
+    ];
+
+    /// Whether this keyword is only reserved in some positions, and is lexed as
+    /// [`TokenType::ContextualKeyword`] rather than [`TokenType::Keyword`]. These are the keywords
+    /// that only start an item or clause, so they can still name fields and variables, such as
+    /// `x.type`.
+    #[must_use]
+    pub fn is_contextual(&self) -> bool {
+        matches!(
+            self,
+            Self::Type | Self::Where | Self::Cardinality | Self::Bytes | Self::Bits
+        )
+    }
+}
+
+impl Literal {
+    /// The type suffix of a numeric literal, if it has one.
+    #[must_use]
+    pub fn suffix(&self) -> Option<Suffix> {
+        match self {
+            Self::Character | Self::String | Self::RawString | Self::Byte | Self::ByteString => {
+                None
+            }
+            Self::DecInt(suffix)
+            | Self::HexInt(suffix)
+            | Self::OctInt(suffix)
+            | Self::BinInt(suffix)
+            | Self::NonInt(suffix) => *suffix,
+        }
+    }
+}
+
+/// A type suffix on a numeric literal, such as the `u8` in `255u8`.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Suffix {
+    pub kind: SuffixKind,
+    pub bits: u8,
+}
+
+impl Suffix {
+    #[must_use]
+    pub fn new(kind: SuffixKind, bits: u8) -> Self {
+        Self { kind, bits }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SuffixKind {
+    Signed,
+    Unsigned,
+    Float,
 }
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
@@ -175,6 +359,51 @@ pub enum Punct {
     ShlPipe,
 }
 
+impl Punct {
+    pub const ALL: &'static [Self] = &[
+        Self::Assign,
+        Self::PlusPlus,
+        Self::MinusMinus,
+        Self::Plus,
+        Self::Minus,
+        Self::Star,
+        Self::Slash,
+        Self::StarStar,
+        Self::Percent,
+        Self::Caret,
+        Self::Not,
+        Self::And,
+        Self::Or,
+        Self::Shl,
+        Self::Shr,
+        Self::Eq,
+        Self::EqEq,
+        Self::Gt,
+        Self::Lt,
+        Self::Ge,
+        Self::Le,
+        Self::At,
+        Self::Underscore,
+        Self::Dot,
+        Self::Comma,
+        Self::Semi,
+        Self::Colon,
+        Self::ColonColon,
+        Self::RArrow,
+        Self::FatArrow,
+        Self::Tilde,
+        Self::ForAll,
+        Self::Exists,
+        Self::PlusPercent,
+        Self::PlusPipe,
+        Self::MinusPipe,
+        Self::MinusPercent,
+        Self::StarPercent,
+        Self::StarPipe,
+        Self::ShlPipe,
+    ];
+}
+
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Delimiter {
     CurlyLeft,
@@ -185,8 +414,62 @@ pub enum Delimiter {
     ParRight,
 }
 
+impl Delimiter {
+    pub const ALL: &'static [Self] = &[
+        Self::CurlyLeft,
+        Self::CurlyRight,
+        Self::SquareLeft,
+        Self::SquareRight,
+        Self::ParLeft,
+        Self::ParRight,
+    ];
+}
+
+/// The error returned when parsing a [`Keyword`], [`Punct`] or [`Delimiter`] from a string that
+/// doesn't spell one.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum ParseKindError {
+    UnknownKeyword(String),
+    UnknownPunct(String),
+    UnknownDelimiter(String),
+}
+
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub enum Comment {
+    /// `// ...`
     Comment,
+    /// `/// ...`
     DocComment,
+    /// `//! ...`, documenting the enclosing item
+    InnerDocComment,
+    /// `/* ... */`, which may nest
+    BlockComment,
+    /// `/** ... */`
+    BlockDocComment,
+}
+
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Trivia {
+    /// A run of whitespace other than line terminators
+    Whitespace,
+    /// `\n`, `\r\n` or a lone `\r`
+    Newline,
+    /// Text skipped over after a lexing error, yielded right after the error
+    Skipped,
+}
+
+/// The pieces of a string literal with embedded expressions, such as `"x = {x + 1}"`, which lexes
+/// as `StrStart`, `Fragment`, `ExprStart`, the tokens of `x + 1`, `ExprEnd` and `StrEnd`.
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Interpolation {
+    /// The opening `"`
+    StrStart,
+    /// Text between the quotes and embedded expressions, with escapes left unprocessed
+    Fragment,
+    /// The `{` opening an embedded expression
+    ExprStart,
+    /// The `}` closing an embedded expression
+    ExprEnd,
+    /// The closing `"`
+    StrEnd,
 }
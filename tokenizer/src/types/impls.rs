@@ -1,6 +1,11 @@
+use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
-use crate::types::defs::{Delimiter, Keyword, Punct};
+use crate::types::defs::{
+    ByteSpan, Comment, Delimiter, Interpolation, Keyword, LineColumn, Literal, ParseKindError,
+    Punct, Span, Suffix, SuffixKind, Token, TokenType, Trivia,
+};
 
 impl Display for Punct {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -84,3 +89,300 @@ impl Display for Keyword {
         }
     }
 }
+
+/// Formats as `line:column`, both counted from 1 as editors and compilers report them.
+impl Display for LineColumn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line + 1, self.column + 1)
+    }
+}
+
+/// Formats as `start-end`, e.g. `1:5-1:9`.
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// Formats as `start..end`, like the equivalent [`Range`](std::ops::Range).
+impl Display for ByteSpan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// Formats as the token type, the escaped lexeme and the span, e.g. `identifier "x" at 1:1-1:2`.
+impl Display for Token<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {:?} at {}", self.token_type, self.lexeme, self.span)
+    }
+}
+
+impl Display for TokenType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Keyword(keyword) => write!(f, "keyword `{keyword}`"),
+            Self::ContextualKeyword(keyword) => write!(f, "contextual keyword `{keyword}`"),
+            Self::Ident => write!(f, "identifier"),
+            Self::ProperIdent => write!(f, "proper identifier"),
+            Self::Literal(literal) => write!(f, "{literal}"),
+            Self::Punct(punct) => write!(f, "punct `{punct}`"),
+            Self::Delimiter(delimiter) => write!(f, "delimiter `{delimiter}`"),
+            Self::Comment(comment) => write!(f, "{comment}"),
+            Self::Interpolation(interpolation) => write!(f, "{interpolation}"),
+            Self::Trivia(trivia) => write!(f, "{trivia}"),
+        }
+    }
+}
+
+/// Formats as e.g. `hex int literal`, followed by the suffix in parentheses if there is one.
+impl Display for Literal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Character => write!(f, "character literal")?,
+            Self::String => write!(f, "string literal")?,
+            Self::RawString => write!(f, "raw string literal")?,
+            Self::Byte => write!(f, "byte literal")?,
+            Self::ByteString => write!(f, "byte string literal")?,
+            Self::DecInt(_) => write!(f, "decimal int literal")?,
+            Self::HexInt(_) => write!(f, "hex int literal")?,
+            Self::OctInt(_) => write!(f, "octal int literal")?,
+            Self::BinInt(_) => write!(f, "binary int literal")?,
+            Self::NonInt(_) => write!(f, "non-int literal")?,
+        }
+        match self.suffix() {
+            Some(suffix) => write!(f, " ({suffix})"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Display for Comment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Comment => write!(f, "comment"),
+            Self::DocComment => write!(f, "doc comment"),
+            Self::InnerDocComment => write!(f, "inner doc comment"),
+            Self::BlockComment => write!(f, "block comment"),
+            Self::BlockDocComment => write!(f, "block doc comment"),
+        }
+    }
+}
+
+impl Display for Interpolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::StrStart => write!(f, "interpolated string start"),
+            Self::Fragment => write!(f, "string fragment"),
+            Self::ExprStart => write!(f, "interpolation start"),
+            Self::ExprEnd => write!(f, "interpolation end"),
+            Self::StrEnd => write!(f, "interpolated string end"),
+        }
+    }
+}
+
+impl Display for Trivia {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Whitespace => write!(f, "whitespace"),
+            Self::Newline => write!(f, "newline"),
+            Self::Skipped => write!(f, "skipped text"),
+        }
+    }
+}
+
+impl Display for Suffix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            SuffixKind::Signed => write!(f, "i{}", self.bits),
+            SuffixKind::Unsigned => write!(f, "u{}", self.bits),
+            SuffixKind::Float => write!(f, "f{}", self.bits),
+        }
+    }
+}
+
+/// Accepts the spellings produced by [`Display`], plus the Unicode aliases the lexer accepts, so
+/// that `punct.to_string().parse() == Ok(punct)` for every punct.
+impl FromStr for Punct {
+    type Err = ParseKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            ":=" | "≔" => Ok(Self::Assign),
+            "++" => Ok(Self::PlusPlus),
+            "--" => Ok(Self::MinusMinus),
+            "+" => Ok(Self::Plus),
+            "-" => Ok(Self::Minus),
+            "*" => Ok(Self::Star),
+            "**" => Ok(Self::StarStar),
+            "/" => Ok(Self::Slash),
+            "%" => Ok(Self::Percent),
+            "^" => Ok(Self::Caret),
+            "!" | "¬" | "不" => Ok(Self::Not),
+            "&" | "∧" => Ok(Self::And),
+            "|" | "∨" => Ok(Self::Or),
+            "<<" => Ok(Self::Shl),
+            ">>" => Ok(Self::Shr),
+            "=" => Ok(Self::Eq),
+            "==" => Ok(Self::EqEq),
+            ">" => Ok(Self::Gt),
+            "<" => Ok(Self::Lt),
+            ">=" | "≥" => Ok(Self::Ge),
+            "<=" | "≤" => Ok(Self::Le),
+            "@" => Ok(Self::At),
+            "_" => Ok(Self::Underscore),
+            "." => Ok(Self::Dot),
+            "," => Ok(Self::Comma),
+            ";" => Ok(Self::Semi),
+            ":" => Ok(Self::Colon),
+            "::" => Ok(Self::ColonColon),
+            "->" => Ok(Self::RArrow),
+            "=>" => Ok(Self::FatArrow),
+            "~" => Ok(Self::Tilde),
+            "∀" => Ok(Self::ForAll),
+            "∃" => Ok(Self::Exists),
+            "+%" => Ok(Self::PlusPercent),
+            "+|" => Ok(Self::PlusPipe),
+            "-%" => Ok(Self::MinusPercent),
+            "-|" => Ok(Self::MinusPipe),
+            "*%" => Ok(Self::StarPercent),
+            "*|" => Ok(Self::StarPipe),
+            "<<|" => Ok(Self::ShlPipe),
+            _ => Err(ParseKindError::UnknownPunct(s.to_owned())),
+        }
+    }
+}
+
+impl TryFrom<&str> for Punct {
+    type Error = ParseKindError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl FromStr for Delimiter {
+    type Err = ParseKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "{" => Ok(Self::CurlyLeft),
+            "}" => Ok(Self::CurlyRight),
+            "[" => Ok(Self::SquareLeft),
+            "]" => Ok(Self::SquareRight),
+            "(" => Ok(Self::ParLeft),
+            ")" => Ok(Self::ParRight),
+            _ => Err(ParseKindError::UnknownDelimiter(s.to_owned())),
+        }
+    }
+}
+
+impl TryFrom<&str> for Delimiter {
+    type Error = ParseKindError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Keyword {
+    /// Returns the keyword spelled `s`, without allocating an error for the lexer's sake.
+    pub(crate) fn from_name(s: &str) -> Option<Self> {
+        match s {
+            "if" => Some(Self::If),
+            "else" => Some(Self::Else),
+            "match" => Some(Self::Match),
+            "loop" => Some(Self::Loop),
+            "let" => Some(Self::Let),
+            "type" => Some(Self::Type),
+            "class" => Some(Self::Class),
+            "ret" => Some(Self::Ret),
+            "where" => Some(Self::Where),
+            "miguel" => Some(Self::Miguel),
+            "kyasig" => Some(Self::Kyasig),
+            "claim" => Some(Self::Claim),
+            "cardinality" => Some(Self::Cardinality),
+            "bytes" => Some(Self::Bytes),
+            "bits" => Some(Self::Bits),
+            "fn" => Some(Self::Fn),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Keyword {
+    type Err = ParseKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s).ok_or_else(|| ParseKindError::UnknownKeyword(s.to_owned()))
+    }
+}
+
+impl TryFrom<&str> for Keyword {
+    type Error = ParseKindError;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl Display for ParseKindError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownKeyword(s) => write!(f, "Unknown keyword: {s:?}"),
+            Self::UnknownPunct(s) => write!(f, "Unknown punct: {s:?}"),
+            Self::UnknownDelimiter(s) => write!(f, "Unknown delimiter: {s:?}"),
+        }
+    }
+}
+
+impl Error for ParseKindError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_round_trips() {
+        for &keyword in Keyword::ALL {
+            assert_eq!(keyword.to_string().parse(), Ok(keyword));
+        }
+        for &punct in Punct::ALL {
+            assert_eq!(punct.to_string().parse(), Ok(punct));
+        }
+        for &delimiter in Delimiter::ALL {
+            assert_eq!(delimiter.to_string().parse(), Ok(delimiter));
+        }
+    }
+
+    #[test]
+    fn aliases_parse() {
+        for (alias, punct) in [
+            ("≔", Punct::Assign),
+            ("∧", Punct::And),
+            ("∨", Punct::Or),
+            ("¬", Punct::Not),
+            ("不", Punct::Not),
+            ("≥", Punct::Ge),
+            ("≤", Punct::Le),
+        ] {
+            assert_eq!(Punct::try_from(alias), Ok(punct));
+        }
+    }
+
+    #[test]
+    fn unknown_spellings_are_errors() {
+        assert_eq!(
+            "iff".parse::<Keyword>(),
+            Err(ParseKindError::UnknownKeyword("iff".to_owned()))
+        );
+        assert_eq!(
+            "<>".parse::<Punct>(),
+            Err(ParseKindError::UnknownPunct("<>".to_owned()))
+        );
+        assert_eq!(
+            "<".parse::<Delimiter>(),
+            Err(ParseKindError::UnknownDelimiter("<".to_owned()))
+        );
+    }
+}